@@ -17,15 +17,121 @@ pub enum LotteryGameError {
     InvalidNumeric,
     #[msg("Invalid lottery ticket amount.")]
     InvalidLotteryTicketAmount,
+    #[msg("Invalid lottery ticket quantity.")]
+    InvalidTicketQuantity,
+    #[msg("Purchase exceeds the lottery game's ticket limit.")]
+    TicketLimitExceeded,
     #[msg("Lottery game is closed.")]
     LotteryGameClosed,
+    #[msg("Lottery game is cancelled.")]
+    LotteryGameCancelled,
+    #[msg("Lottery game is not cancelled.")]
+    LotteryGameNotCancelled,
+    #[msg("Invalid sales window or draw time.")]
+    InvalidSchedule,
+    #[msg("Ticket sales have not opened.")]
+    SalesNotOpen,
+    #[msg("Ticket sales have closed.")]
+    SalesClosed,
+    #[msg("Draw time has not been reached.")]
+    DrawTooEarly,
     #[msg("Invalid lottery game winner.")]
     InvalidLotteryGameWinner,
+    #[msg("Invalid percentage, expected basis points between 0 and 10000.")]
+    InvalidPercentage,
+    #[msg("Invalid prize tiers.")]
+    InvalidPrizeTiers,
+    #[msg("Game mode is not supported by this instruction.")]
+    InvalidGameMode,
+    #[msg("Invalid number range.")]
+    InvalidNumberRange,
+    #[msg("Invalid lottery numbers.")]
+    InvalidLotteryNumbers,
+    #[msg("Invalid revenue account.")]
+    InvalidRevenueAccount,
+    #[msg("Token mint or token account doesn't match the lottery game's mint.")]
+    InvalidMint,
+    #[msg("Amount received by the vault doesn't cover the ticket price.")]
+    TransferAmountMismatch,
+    #[msg("Accounts don't match the lottery game's payment mode.")]
+    InvalidPaymentMode,
+    #[msg("Treasury vault doesn't belong to the lottery game.")]
+    InvalidTreasuryVault,
+    #[msg("Lottery round proceeds are already distributed.")]
+    ProceedsAlreadyDistributed,
+    #[msg("Revealed seed does not match the draw commitment.")]
+    InvalidDrawCommitment,
+    #[msg("Lottery game draw is not pending.")]
+    DrawNotPending,
+    #[msg("Reveal window has expired.")]
+    RevealWindowExpired,
+    #[msg("Reveal window is still open.")]
+    RevealWindowOpen,
+    #[msg("Draw entropy is unavailable.")]
+    DrawEntropyUnavailable,
+    #[msg("Draw method is not supported by this instruction.")]
+    InvalidDrawMethod,
+    #[msg("Invalid oracle authority.")]
+    InvalidOracleAuthority,
+    #[msg("Randomness request does not match the pending request.")]
+    InvalidRandomnessRequest,
+
+    // Lottery round
+    #[msg("Lottery round is not settled.")]
+    LotteryRoundNotSettled,
+    #[msg("Lottery round has no funds to roll over.")]
+    NothingToRollOver,
+    #[msg("Rollover is not allowed by the lottery game rollover rule.")]
+    RolloverNotAllowed,
+    #[msg("Claim period has not ended.")]
+    ClaimPeriodNotEnded,
+    #[msg("Unclaimed prizes can only roll over after a claim period.")]
+    InvalidClaimPeriod,
+    #[msg("Claim deadline has passed.")]
+    ClaimDeadlinePassed,
+    #[msg("Claim deadline has not passed.")]
+    ClaimDeadlineNotPassed,
+    #[msg("Claim period can't end before the claim deadline.")]
+    InvalidClaimDeadline,
+    #[msg("Lottery round has no unclaimed prizes to sweep.")]
+    NothingToSweep,
+    #[msg("Lottery round has no carried jackpot to release.")]
+    NothingToRelease,
+    #[msg("Invalid unclaimed prizes beneficiary account.")]
+    InvalidBeneficiaryAccount,
+    #[msg("No fees to withdraw.")]
+    NoFeesToWithdraw,
+    #[msg("Invalid fee account.")]
+    InvalidFeeAccount,
+    #[msg("Prize has already been claimed.")]
+    PrizeAlreadyClaimed,
+    #[msg("Prize can only be paid into the winner's associated token account.")]
+    InvalidWinnerTokenAccount,
+    #[msg("Lottery round tickets are not being tallied.")]
+    TallyNotPending,
+    #[msg("Tickets must be tallied in ticket number order.")]
+    InvalidTallyOrder,
+    #[msg("Lottery round is not refundable.")]
+    RoundNotRefundable,
+    #[msg("Ticket has already been refunded.")]
+    TicketAlreadyRefunded,
+    #[msg("Lottery round winning numbers are already drawn.")]
+    LotteryRoundAlreadyDrawn,
 
     //
     #[msg("Invalid country length")]
     InvalidCountryLength,
 
+    // lottery operator
+    #[msg("Lottery operator is suspended.")]
+    OperatorSuspended,
+    #[msg("Lottery operator doesn't match the lottery game's operator.")]
+    InvalidOperator,
+    #[msg("Invalid licensed countries.")]
+    InvalidLicensedCountries,
+    #[msg("Lottery operator is not licensed in the country.")]
+    CountryNotLicensed,
+
     // Arithmetic
     #[msg("Arithmetic operation failed.")]
     InvalidArithmeticOperation,
@@ -40,9 +146,45 @@ pub enum LotteryGameError {
     #[msg("Participant(s) missing.")]
     InvalidParticipants,
 
+    // roles
+    #[msg("Signer is not the program's upgrade authority.")]
+    InvalidUpgradeAuthority,
+    #[msg("Signer is not the super admin.")]
+    InvalidSuperAdmin,
+    #[msg("Signer is not the pending super admin.")]
+    InvalidPendingSuperAdmin,
+    #[msg("Signer is not an operator authority.")]
+    MissingOperatorRole,
+    #[msg("Signer is not a draw authority.")]
+    MissingDrawRole,
+    #[msg("Invalid authority.")]
+    InvalidAuthority,
+    #[msg("Role is already granted to the authority.")]
+    RoleAlreadyGranted,
+    #[msg("Role is not granted to the authority.")]
+    RoleNotGranted,
+    #[msg("Role can't be granted to any more authorities.")]
+    TooManyAuthorities,
+    #[msg("Signer is not a guardian authority.")]
+    MissingGuardianRole,
+
+    // emergency pause
+    #[msg("Platform is paused.")]
+    PlatformPaused,
+    #[msg("Lottery game is paused.")]
+    LotteryGamePaused,
+    #[msg("Pause state is unchanged.")]
+    PauseStateUnchanged,
+
     // account
     #[msg("Account is not initialized.")]
     AccountNotInitialized,
     #[msg("Account is already initialized.")]
     AccountAlreadyInitialized,
+    #[msg("Account is not in the layout being migrated from.")]
+    InvalidMigration,
+    #[msg("Account is already migrated.")]
+    AccountAlreadyMigrated,
+    #[msg("Token mint has an extension lottery games can't be paid with.")]
+    UnsupportedMintExtension,
 }
@@ -0,0 +1,334 @@
+use crate::state::{
+    configs::AdminRole,
+    lottery_game::{DrawMethod, GameMode, PaymentMode},
+    lottery_operator::OperatorStatus,
+    lottery_round::{LotteryRound, LotteryRoundStatus, TierResult, WinningTicket},
+};
+use anchor_lang::prelude::*;
+
+// amounts are in base units of the lottery game's mint, or lamports
+
+// configs
+#[event]
+pub struct ConfigsInitialized {
+    pub super_admin: Pubkey, // upgrade authority initializing the configs
+    pub timestamp: i64,      // unix timestamp of the change
+}
+
+#[event]
+pub struct ConfigsMigrated {
+    pub super_admin: Pubkey,           // upgrade authority migrating the configs
+    pub legacy_operators: Vec<String>, // operator names of the original layout, to be registered again
+    pub timestamp: i64,                // unix timestamp of the migration
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: AdminRole,     // role granted
+    pub authority: Pubkey,   // signer the role is granted to
+    pub super_admin: Pubkey, // super admin granting the role
+    pub timestamp: i64,      // unix timestamp of the change
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: AdminRole,     // role revoked
+    pub authority: Pubkey,   // signer the role is revoked from
+    pub super_admin: Pubkey, // super admin revoking the role
+    pub timestamp: i64,      // unix timestamp of the change
+}
+
+#[event]
+pub struct SuperAdminProposed {
+    pub super_admin: Pubkey,         // current super admin
+    pub pending_super_admin: Pubkey, // proposed super admin, has to accept before taking over
+    pub timestamp: i64,              // unix timestamp of the change
+}
+
+#[event]
+pub struct SuperAdminAccepted {
+    pub previous_super_admin: Pubkey, // super admin handing over
+    pub super_admin: Pubkey,          // new super admin
+    pub timestamp: i64,               // unix timestamp of the change
+}
+
+#[event]
+pub struct FeeConfigsUpdated {
+    pub operator_fee_percentage: u32, // default operator fee of new lottery games (basis points)
+    pub protocol_fee_percentage: u32, // protocol fee of new lottery games (basis points)
+    pub protocol_fee_account: Pubkey, // wallet receiving the protocol fees
+    pub super_admin: Pubkey,          // super admin updating the fees
+    pub timestamp: i64,               // unix timestamp of the change
+}
+
+// lottery operator
+#[event]
+pub struct OperatorRegistered {
+    pub lottery_operator: Pubkey,        // lottery operator account
+    pub authority: Pubkey, // signer registering and running the operator's lottery games
+    pub name: String,      // company operating the lottery
+    pub licensed_countries: Vec<String>, // countries the operator can run lottery games in
+    pub timestamp: i64,    // unix timestamp of the registration
+}
+
+#[event]
+pub struct OperatorStatusUpdated {
+    pub lottery_operator: Pubkey, // lottery operator account
+    pub status: OperatorStatus,   // new status of the operator
+    pub super_admin: Pubkey,      // super admin updating the status
+    pub timestamp: i64,           // unix timestamp of the change
+}
+
+// emergency pause
+#[event]
+pub struct PlatformPauseUpdated {
+    pub guardian: Pubkey, // guardian authority pausing or unpausing the platform
+    pub paused: bool,     // whether ticket sales and draws of all lottery games are blocked
+    pub reason_code: u16, // reason for the change, as coded by the guardians
+    pub timestamp: i64,   // unix timestamp of the change
+}
+
+#[event]
+pub struct LotteryGamePauseUpdated {
+    pub lottery_game: Pubkey, // lottery game paused or unpaused
+    pub guardian: Pubkey,     // guardian authority pausing or unpausing the lottery game
+    pub paused: bool,         // whether ticket sales and draws of the lottery game are blocked
+    pub reason_code: u16,     // reason for the change, as coded by the guardians
+    pub timestamp: i64,       // unix timestamp of the change
+}
+
+// lottery game
+#[event]
+pub struct GameRegistered {
+    pub lottery_game: Pubkey,             // lottery game account
+    pub owner: Pubkey,                    // lottery game admin
+    pub game_id: u64,     // id of the lottery game, scoped to the lottery game admin
+    pub operator: Pubkey, // lottery operator account running the lottery game
+    pub country: String,  // home country where lottery game is auctioned
+    pub lottery_game_name: String, // lottery game name
+    pub payment_mode: PaymentMode, // currency the lottery game's tickets are paid in
+    pub mint: Pubkey,     // token mint accepted by the lottery game (token)
+    pub unit_cost_of_lottery_ticket: u64, // unit cost of lottery ticket
+    pub operator_fee_percentage: u32, // share of every ticket sale charged as the operator fee (basis points)
+    pub protocol_fee_percentage: u32, // share of every ticket sale charged as the protocol fee (basis points)
+    pub draw_method: DrawMethod,      // source of the draw randomness
+    pub game_mode: GameMode,          // how the round winners are determined
+    pub sales_open_at: i64, // unix timestamp from which the first round's tickets can be bought
+    pub sales_close_at: i64, // unix timestamp from which the first round's tickets can no longer be bought
+    pub draw_at: i64,        // unix timestamp from which the first round can be drawn
+    pub timestamp: i64,      // unix timestamp of the registration
+}
+
+#[event]
+pub struct GameMigrated {
+    pub lottery_game: Pubkey,        // lottery game account
+    pub legacy_lottery_game: Pubkey, // lottery game account of the original layout, now closed
+    pub owner: Pubkey,               // lottery game admin
+    pub game_id: u64,                // id of the lottery game, scoped to the lottery game admin
+    pub operator: Pubkey,            // lottery operator account running the lottery game
+    pub winnings_paid: u64,          // unclaimed prize paid to the legacy winner
+    pub refunds_paid: u64,           // ticket prices refunded for a legacy game never drawn
+    pub timestamp: i64,              // unix timestamp of the migration
+}
+
+#[event]
+pub struct GameCancelled {
+    pub lottery_game: Pubkey,             // lottery game cancelled
+    pub round_number: u64,                // current round of the lottery game
+    pub round_status: LotteryRoundStatus, // status the current round was left in
+    pub timestamp: i64,                   // unix timestamp of the cancellation
+}
+
+#[event]
+pub struct CancelledJackpotReleased {
+    pub lottery_game: Pubkey, // cancelled lottery game
+    pub round_number: u64,    // final round the jackpot was carried into
+    pub reserve: Pubkey,      // reserve account receiving the jackpot
+    pub amount: u64,          // carried jackpot released to the reserve
+    pub timestamp: i64,       // unix timestamp of the release
+}
+
+#[event]
+pub struct RoundStarted {
+    pub lottery_game: Pubkey,                      // lottery game of the round
+    pub round_number: u64,                         // round started
+    pub previous_round_status: LotteryRoundStatus, // status the previous round ended in
+    pub rollover_amount: u64,                      // jackpot rolled over from the previous round
+    pub sales_open_at: i64,  // unix timestamp from which tickets can be bought
+    pub sales_close_at: i64, // unix timestamp from which tickets can no longer be bought
+    pub draw_at: i64,        // unix timestamp from which the round can be drawn
+    pub timestamp: i64,      // unix timestamp at which the round was started
+}
+
+// participant
+#[event]
+pub struct ParticipantRegistered {
+    pub participant: Pubkey, // participant account
+    pub owner: Pubkey,       // signer owning the participant account
+    pub country: String,     // home country of participant
+    pub timestamp: i64,      // unix timestamp of the registration
+}
+
+#[event]
+pub struct ParticipantMigrated {
+    pub participant: Pubkey,             // participant account
+    pub owner: Pubkey,                   // signer owning the participant account
+    pub total_units_lottery_ticket: u64, // tickets bought before the migration
+    pub total_amount_spent: u64,         // amount spent before the migration
+    pub timestamp: i64,                  // unix timestamp of the migration
+}
+
+// ticket sales
+#[event]
+pub struct TicketPurchased {
+    pub lottery_game: Pubkey,   // lottery game the ticket was bought in
+    pub round_number: u64,      // round the ticket takes part in
+    pub participant: Pubkey,    // participant account of the buyer
+    pub owner: Pubkey,          // buyer
+    pub ticket: Pubkey,         // ticket account
+    pub ticket_number: u64,     // first ticket number issued to the buyer
+    pub quantity: u64,          // number of tickets (entries) bought
+    pub amount: u64,            // amount paid for all the tickets
+    pub prize_pool_amount: u64, // part of the amount paid into the prize pool
+    pub operator_fee: u64,      // part of the amount charged as the operator fee
+    pub protocol_fee: u64,      // part of the amount charged as the protocol fee
+    pub numbers: Vec<u8>,       // numbers played by every entry of the ticket (pick numbers)
+    pub timestamp: i64,         // unix timestamp of the purchase
+}
+
+// draws
+#[event]
+pub struct DrawRequested {
+    pub lottery_game: Pubkey,       // lottery game being drawn
+    pub round_number: u64,          // round being drawn
+    pub draw_method: DrawMethod,    // source of the draw randomness
+    pub draw_slot: u64,             // slot at which ticket sales were closed for the draw
+    pub randomness_request_id: u64, // id of the oracle randomness request (oracle)
+    pub tickets_sold: u64,          // number of tickets sold in the round
+    pub amounts_raised: u64,        // amounts raised from sale of the round's tickets
+    pub timestamp: i64,             // unix timestamp at which sales were closed
+}
+
+#[event]
+pub struct WinnerDrawn {
+    pub lottery_game: Pubkey,                // lottery game drawn
+    pub round_number: u64,                   // round drawn
+    pub randomness: [u8; 32],                // randomness the round winners were derived from
+    pub winning_tickets: Vec<WinningTicket>, // tickets drawn as winners, in prize tier order (raffle)
+    pub winning_numbers: Vec<u8>,            // winning combination (pick numbers)
+    pub tier_results: Vec<TierResult>,       // winners and prize of each prize tier (pick numbers)
+    pub prize_amount: u64,                   // prize pool shared among the round's prize tiers
+    pub unwon_amount: u64, // prize shares of the tiers nobody matched (pick numbers)
+    pub operator_revenue_amount: u64, // share of the round's ticket sales owed to the operator
+    pub reserve_amount: u64, // share of the round's ticket sales owed to the reserve
+    pub operator_fees: u64, // operator fees released by the draw
+    pub protocol_fees: u64, // protocol fees released by the draw
+    pub drawn_at: i64,     // unix timestamp at which the winners were drawn
+}
+
+impl WinnerDrawn {
+    // Results of a round whose winners are all known.
+    pub fn new(lottery_round: &LotteryRound) -> Self {
+        Self {
+            lottery_game: lottery_round.lottery_game,
+            round_number: lottery_round.round_number,
+            randomness: lottery_round.randomness,
+            winning_tickets: lottery_round.winning_tickets.clone(),
+            winning_numbers: lottery_round.winning_numbers.clone(),
+            tier_results: lottery_round.tier_results.clone(),
+            prize_amount: lottery_round.prize_amount,
+            unwon_amount: lottery_round.unwon_amount,
+            operator_revenue_amount: lottery_round.operator_revenue_amount,
+            reserve_amount: lottery_round.reserve_amount,
+            operator_fees: lottery_round.operator_fees,
+            protocol_fees: lottery_round.protocol_fees,
+            drawn_at: lottery_round.drawn_at,
+        }
+    }
+}
+
+#[event]
+pub struct RoundRefundable {
+    pub lottery_game: Pubkey, // lottery game of the round
+    pub round_number: u64,    // round whose tickets are refunded instead of drawn
+    pub tickets_sold: u64,    // number of tickets sold in the round
+    pub amounts_raised: u64,  // amounts raised from sale of the round's tickets, owed back
+    pub timestamp: i64,       // unix timestamp at which the round became refundable
+}
+
+impl RoundRefundable {
+    // Round whose ticket sales are owed back to the ticket holders.
+    pub fn new(lottery_round: &LotteryRound, timestamp: i64) -> Self {
+        Self {
+            lottery_game: lottery_round.lottery_game,
+            round_number: lottery_round.round_number,
+            tickets_sold: lottery_round.tickets_sold,
+            amounts_raised: lottery_round.amounts_raised,
+            timestamp,
+        }
+    }
+}
+
+// payouts
+#[event]
+pub struct WinningsWithdrawn {
+    pub lottery_game: Pubkey, // lottery game the prize was won in
+    pub round_number: u64,    // round the prize was won in
+    pub ticket_number: u64,   // winning ticket
+    pub owner: Pubkey,        // winner
+    pub amount: u64,          // prize paid to the winner
+    pub timestamp: i64,       // unix timestamp of the withdrawal
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub lottery_game: Pubkey, // lottery game the ticket was bought in
+    pub round_number: u64,    // refundable round of the ticket
+    pub ticket_number: u64,   // refunded ticket
+    pub owner: Pubkey,        // ticket holder
+    pub amount: u64,          // amount refunded to the ticket holder
+    pub timestamp: i64,       // unix timestamp of the refund
+}
+
+#[event]
+pub struct UnclaimedPrizeRolledOver {
+    pub lottery_game: Pubkey,        // lottery game of the rounds
+    pub unclaimed_round_number: u64, // round whose unclaimed prize rolled over
+    pub current_round_number: u64,   // round whose jackpot received the prize
+    pub amount: u64,                 // unclaimed prize rolled over
+    pub timestamp: i64,              // unix timestamp of the rollover
+}
+
+#[event]
+pub struct UnclaimedPrizesSwept {
+    pub lottery_game: Pubkey, // lottery game of the round
+    pub round_number: u64,    // round whose unclaimed prizes were swept
+    pub beneficiary: Pubkey,  // token account (or wallet, sol) receiving the unclaimed prizes
+    pub amount: u64,          // unclaimed prizes swept
+    pub timestamp: i64,       // unix timestamp of the sweep
+}
+
+#[event]
+pub struct RoundProceedsDistributed {
+    pub lottery_game: Pubkey,         // lottery game of the round
+    pub round_number: u64,            // round whose proceeds were distributed
+    pub operator_revenue_amount: u64, // operator revenue paid to the operator revenue account
+    pub reserve_amount: u64,          // reserve paid to the reserve account
+    pub timestamp: i64,               // unix timestamp of the distribution
+}
+
+#[event]
+pub struct OperatorFeesWithdrawn {
+    pub lottery_game: Pubkey, // lottery game the fees were charged in
+    pub recipient: Pubkey,    // operator revenue account receiving the fees
+    pub amount: u64,          // operator fees withdrawn
+    pub timestamp: i64,       // unix timestamp of the withdrawal
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub lottery_game: Pubkey, // lottery game the fees were charged in
+    pub recipient: Pubkey,    // protocol fee wallet (its associated token account, token)
+    pub amount: u64,          // protocol fees withdrawn
+    pub timestamp: i64,       // unix timestamp of the withdrawal
+}
@@ -1,18 +1,54 @@
 // admin instructions
+pub mod accept_super_admin;
+pub mod cancel_lottery_game;
+pub mod grant_role;
 pub mod init;
+pub mod migrate_configs;
+pub mod migrate_lottery_game;
+pub mod propose_super_admin;
 pub mod register_lottery_game;
+pub mod register_operator;
+pub mod request_lottery_game_randomness;
+pub mod reveal_lottery_game_winner;
+pub mod revoke_role;
+pub mod set_lottery_game_paused;
+pub mod set_platform_paused;
+pub mod start_next_round;
+pub mod sweep_unclaimed_prizes;
+pub mod update_fee_configs;
+pub mod update_operator_status;
+pub mod withdraw_operator_fees;
+pub mod withdraw_protocol_fees;
 
 // public instructions
 pub mod buy_lottery_ticket;
+pub mod claim_refund;
 pub mod create_token;
+pub mod distribute_lottery_round_proceeds;
+pub mod expire_lottery_game_draw;
 pub mod get_lottery_game_winner;
+pub mod migrate_participant;
 pub mod register_participant;
+pub mod release_cancelled_jackpot;
+pub mod rollover_unclaimed_prize;
+pub mod tally_lottery_round_tickets;
 pub mod transfer_token;
 pub mod withdraw_lottery_game_winnings;
 
+// oracle instructions
+pub mod fulfil_lottery_game_randomness;
+
 // bring everything in scope
 pub use {
-    buy_lottery_ticket::*, create_token::*, get_lottery_game_winner::*, init::*,
-    register_lottery_game::*, register_participant::*, transfer_token::*,
-    withdraw_lottery_game_winnings::*,
+    accept_super_admin::*, buy_lottery_ticket::*, cancel_lottery_game::*, claim_refund::*,
+    create_token::*, distribute_lottery_round_proceeds::*, expire_lottery_game_draw::*,
+    fulfil_lottery_game_randomness::*, get_lottery_game_winner::*, grant_role::*, init::*,
+    migrate_configs::*, migrate_lottery_game::*, migrate_participant::*, propose_super_admin::*,
+    register_lottery_game::*, register_operator::*, register_participant::*,
+    release_cancelled_jackpot::*, request_lottery_game_randomness::*,
+    reveal_lottery_game_winner::*, revoke_role::*, rollover_unclaimed_prize::*,
+    set_lottery_game_paused::*, set_platform_paused::*, start_next_round::*,
+    sweep_unclaimed_prizes::*, tally_lottery_round_tickets::*, transfer_token::*,
+    update_fee_configs::*, update_operator_status::*, withdraw_lottery_game_winnings::*,
+    withdraw_operator_fees::*, withdraw_protocol_fees::*,
 };
@@ -0,0 +1,39 @@
+//! AcceptSuperAdmin instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError, events::SuperAdminAccepted, state::configs::LotteryGameConfigs,
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct AcceptSuperAdmin<'info> {
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.pending_super_admin == owner.key() @ LotteryGameError::InvalidPendingSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // the proposed super admin
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn accept_super_admin(ctx: Context<AcceptSuperAdmin>) -> Result<()> {
+    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+    let previous_super_admin = lottery_game_configs.super_admin;
+    lottery_game_configs.super_admin = *ctx.accounts.owner.key;
+    lottery_game_configs.pending_super_admin = Pubkey::default();
+
+    let clock = Clock::get()?;
+    emit!(SuperAdminAccepted {
+        previous_super_admin,
+        super_admin: lottery_game_configs.super_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
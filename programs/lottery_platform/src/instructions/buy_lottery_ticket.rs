@@ -3,43 +3,110 @@
 use {
     crate::{
         error::LotteryGameError,
-        state::{lottery_game::LotteryGame, participant::Participant},
+        events::TicketPurchased,
+        state::{
+            configs::LotteryGameConfigs,
+            game_entry::GameEntry,
+            lottery_game::{GameMode, LotteryGame, PaymentMode},
+            lottery_round::{apply_basis_points, draw_numbers, LotteryRound, LotteryRoundStatus},
+            participant::Participant,
+            ticket::Ticket,
+        },
+    },
+    anchor_lang::{
+        prelude::*,
+        system_program::{transfer, Transfer},
     },
-    anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token_interface::{
+            spl_token_2022::{
+                extension::{
+                    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+                },
+                state::Mint as MintState,
+            },
+            transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+        },
     },
+    solana_program::{hash::hashv, sysvar::clock::Clock},
 };
 
 #[derive(Accounts)]
 #[instruction(params: BuyLotteryTicketParams)]
 pub struct BuyLotteryTicket<'info> {
+    // ticket sales are blocked while the platform is paused
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
     #[account(mut,
-        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = !lottery_game.is_cancelled @ LotteryGameError::LotteryGameCancelled,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
     )]
     pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Open @ LotteryGameError::LotteryGameClosed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
     #[account(mut,has_one = owner,
         constraint = participant.active @ LotteryGameError::InvalidParticipantStatus
     )]
     pub participant: Account<'info, Participant>,
-    #[account(mut)]
-    pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub mint_token: Account<'info, Mint>,
+    // created on the participant's first purchase in the lottery game
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GameEntry::INIT_SPACE,
+        seeds = [b"game-entry", lottery_game.key().as_ref(), participant.key().as_ref()],
+        bump
+    )]
+    pub game_entry: Account<'info, GameEntry>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Ticket::INIT_SPACE,
+        seeds = [b"ticket", lottery_game.key().as_ref(), lottery_game.tickets_sold.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    // token accounts are only passed by token lottery games
+    #[account(mut,
+        constraint = sender_tokens.mint == lottery_game.mint @ LotteryGameError::InvalidMint
+    )]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    // ticket payments can only go to the lottery game's vault
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub recipient_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // lamports of sol lottery games are paid into the lottery game's treasury vault
+    #[account(mut, address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault)]
+    pub treasury_vault: Option<SystemAccount<'info>>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub associate_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct BuyLotteryTicketParams {
-    pub amount: u32,
+    pub amount: u64,   // price of all the tickets in base units of the mint, or lamports
+    pub quantity: u64, // number of tickets (entries) bought
+    pub numbers: Vec<u8>, // numbers picked by the participant (pick numbers)
+    pub quick_pick: bool, // generate the numbers on-chain instead (pick numbers)
 }
 
 pub fn buy_lottery_ticket(
@@ -51,39 +118,185 @@ pub fn buy_lottery_ticket(
         return Err(LotteryGameError::InvalidAmount.into());
     }
 
+    if params.quantity == 0 {
+        return Err(LotteryGameError::InvalidTicketQuantity.into());
+    }
+
     let sender = &ctx.accounts.owner;
-    let sender_tokens = &ctx.accounts.sender_tokens;
-    let recipient_tokens = &ctx.accounts.recipient_tokens;
-    let token_program = &ctx.accounts.token_program;
     let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
     let participant = &mut ctx.accounts.participant;
-    let unit_cost_of_lottery_ticket: u32 = lottery_game.unit_cost_of_lottery_ticket;
+    let unit_cost_of_lottery_ticket: u64 = lottery_game.unit_cost_of_lottery_ticket;
     let total_amounts_raised = lottery_game.total_amounts_raised;
     let total_available_funds = lottery_game.total_available_funds;
-    let total_units_lottery_ticket: u32 = participant.total_units_lottery_ticket;
-    let available_funds: u32 = participant.available_funds;
-    let decimals = lottery_game.decimals as u64;
+    let total_units_lottery_ticket: u64 = participant.total_units_lottery_ticket;
+    let total_amount_spent: u64 = participant.total_amount_spent;
     let _amount = params.amount;
+    let quantity = params.quantity;
 
-    if _amount != unit_cost_of_lottery_ticket {
-        return Err(LotteryGameError::InvalidLotteryTicketAmount.into());
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < lottery_round.sales_open_at {
+        return Err(LotteryGameError::SalesNotOpen.into());
+    }
+
+    if clock.unix_timestamp >= lottery_round.sales_close_at {
+        return Err(LotteryGameError::SalesClosed.into());
+    }
+
+    let max_tickets_per_purchase = lottery_game.max_tickets_per_purchase;
+    if max_tickets_per_purchase > 0 && quantity > max_tickets_per_purchase {
+        return Err(LotteryGameError::TicketLimitExceeded.into());
+    }
+
+    // the cap counts every ticket the lottery game has sold, across all its rounds
+    let game_tickets_sold = lottery_game
+        .tickets_sold
+        .checked_add(quantity)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    let max_tickets_per_game = lottery_game.max_tickets_per_game;
+    if max_tickets_per_game > 0 && game_tickets_sold > max_tickets_per_game {
+        return Err(LotteryGameError::TicketLimitExceeded.into());
     }
 
-    // Get unit_lottery_ticket from the product of unit_cost_of_lottery_ticket and _amount
-    let unit_lottery_ticket = unit_cost_of_lottery_ticket
-        .checked_mul(_amount)
+    // amounts are in base units of the mint, or lamports
+    let ticket_price = unit_cost_of_lottery_ticket
+        .checked_mul(quantity)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    // Increment total_units_lottery_ticket with new unit_lottery_ticket
+    if _amount != ticket_price {
+        return Err(LotteryGameError::InvalidLotteryTicketAmount.into());
+    }
+
+    let amount_received = match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(recipient_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (
+                &ctx.accounts.sender_tokens,
+                &mut ctx.accounts.recipient_tokens,
+                &ctx.accounts.mint_token,
+                &ctx.accounts.token_program,
+            )
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            // all the tickets are paid in one transfer, the buyer pays any transfer fee
+            // on top so the vault receives the full ticket price
+            let transfer_fee = inverse_transfer_fee(mint_token, ticket_price)?;
+            let transfer_amount = ticket_price
+                .checked_add(transfer_fee)
+                .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+            let vault_balance = recipient_tokens.amount;
+
+            transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: sender_tokens.to_account_info(),
+                        mint: mint_token.to_account_info(),
+                        to: recipient_tokens.to_account_info(),
+                        authority: sender.to_account_info(),
+                    },
+                ),
+                transfer_amount,
+                mint_token.decimals,
+            )?;
+
+            // pools are credited with the amount the vault actually received, not the amount sent
+            recipient_tokens.reload()?;
+            recipient_tokens
+                .amount
+                .checked_sub(vault_balance)
+                .ok_or(LotteryGameError::InvalidArithmeticOperation)?
+        }
+        PaymentMode::Sol => {
+            let Some(treasury_vault) = &ctx.accounts.treasury_vault else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: sender.to_account_info(),
+                        to: treasury_vault.to_account_info(),
+                    },
+                ),
+                ticket_price,
+            )?;
+
+            ticket_price
+        }
+    };
+
+    if amount_received < ticket_price {
+        return Err(LotteryGameError::TransferAmountMismatch.into());
+    }
+
+    let _amount = amount_received;
+
+    // Increment total_units_lottery_ticket with the number of tickets bought
     participant.total_units_lottery_ticket = total_units_lottery_ticket
-        .checked_add(unit_lottery_ticket)
+        .checked_add(quantity)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    // Increment total_amount_spent with new _amount
+    participant.total_amount_spent = total_amount_spent
+        .checked_add(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    // Increment available_funds with new _amount
-    participant.available_funds = available_funds
+    // the participant's tickets and spend in this lottery game
+    let game_entry = &mut ctx.accounts.game_entry;
+    if !game_entry.is_initialized {
+        game_entry.owner = *sender.key;
+        game_entry.participant = participant.key();
+        game_entry.lottery_game = lottery_game.key();
+        game_entry.bump = ctx.bumps.game_entry;
+        game_entry.is_initialized = true;
+    }
+    game_entry.tickets_bought = game_entry
+        .tickets_bought
+        .checked_add(quantity)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    game_entry.amount_spent = game_entry
+        .amount_spent
         .checked_add(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
+    // Split the purchase into the prize pool, the operator fee and the protocol fee. The
+    // fees are held in the round until it is drawn, so refunded tickets are refunded in full
+    let operator_fee = apply_basis_points(_amount, lottery_game.operator_fee_percentage)?;
+    let protocol_fee = apply_basis_points(_amount, lottery_game.protocol_fee_percentage)?;
+    let prize_pool_amount = _amount
+        .checked_sub(operator_fee)
+        .and_then(|amount| amount.checked_sub(protocol_fee))
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    lottery_game.total_prize_pool_amounts = lottery_game
+        .total_prize_pool_amounts
+        .checked_add(prize_pool_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_operator_fees = lottery_game
+        .total_operator_fees
+        .checked_add(operator_fee)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_protocol_fees = lottery_game
+        .total_protocol_fees
+        .checked_add(protocol_fee)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.operator_fees = lottery_round
+        .operator_fees
+        .checked_add(operator_fee)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.protocol_fees = lottery_round
+        .protocol_fees
+        .checked_add(protocol_fee)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
     // Increment total_amounts_raised with new _amount
     lottery_game.total_amounts_raised = total_amounts_raised
         .checked_add(_amount)
@@ -94,29 +307,105 @@ pub fn buy_lottery_ticket(
         .checked_add(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    let base: u32 = 10;
-    let exponent = lottery_game.decimals as u32;
-
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    // Increment the round's amounts_raised and available_funds with new _amount
+    lottery_round.amounts_raised = lottery_round
+        .amounts_raised
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.available_funds = lottery_round
+        .available_funds
+        .checked_add(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    lottery_game.participants.push(*sender.key);
+    let numbers = match lottery_game.game_mode {
+        GameMode::Raffle => {
+            if !params.numbers.is_empty() || params.quick_pick {
+                return Err(LotteryGameError::InvalidLotteryNumbers.into());
+            }
+            Vec::new()
+        }
+        GameMode::PickNumbers if params.quick_pick => {
+            if !params.numbers.is_empty() {
+                return Err(LotteryGameError::InvalidLotteryNumbers.into());
+            }
 
-    transfer(
-        CpiContext::new(
-            token_program.to_account_info(),
-            Transfer {
-                from: sender_tokens.to_account_info(),
-                to: recipient_tokens.to_account_info(),
-                authority: sender.to_account_info(),
-            },
-        ),
-        _amount,
-    )?;
+            // quick picks need no secrecy, the buyer can predict them from these inputs
+            // but the draw doesn't depend on them; every entry plays the same numbers
+            let entropy = hashv(&[
+                sender.key.as_ref(),
+                lottery_game.key().as_ref(),
+                &lottery_game.tickets_sold.to_le_bytes(),
+                &clock.slot.to_le_bytes(),
+            ])
+            .to_bytes();
+            draw_numbers(&entropy, lottery_game.pick_count, lottery_game.number_range)
+        }
+        GameMode::PickNumbers => {
+            let mut numbers = params.numbers.clone();
+            numbers.sort_unstable();
+
+            // exactly pick_count distinct numbers between 1 and number_range
+            let is_distinct = numbers.windows(2).all(|pair| pair[0] < pair[1]);
+            if numbers.len() == lottery_game.pick_count as usize
+                && is_distinct
+                && numbers.first() >= Some(&1)
+                && numbers.last() <= Some(&lottery_game.number_range)
+            {
+            } else {
+                return Err(LotteryGameError::InvalidLotteryNumbers.into());
+            }
+            numbers
+        }
+    };
+
+    // Issue the next `quantity` sequential ticket numbers to the buyer, each one is an
+    // entry in the draw so the buyer's odds grow with the number of tickets bought
+    let ticket = &mut ctx.accounts.ticket;
+    ticket.owner = *sender.key;
+    ticket.lottery_game = lottery_game.key();
+    ticket.ticket_number = lottery_game.tickets_sold;
+    ticket.quantity = quantity;
+    ticket.round_number = lottery_round.round_number;
+    ticket.amount_paid = _amount;
+    ticket.numbers = numbers;
+    ticket.is_initialized = true;
+
+    lottery_game.tickets_sold = game_tickets_sold;
+    lottery_round.tickets_sold = lottery_round
+        .tickets_sold
+        .checked_add(quantity)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    emit!(TicketPurchased {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        participant: participant.key(),
+        owner: *sender.key,
+        ticket: ticket.key(),
+        ticket_number: ticket.ticket_number,
+        quantity,
+        amount: _amount,
+        prize_pool_amount,
+        operator_fee,
+        protocol_fee,
+        numbers: ticket.numbers.clone(),
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
+
+// fee withheld by a Token-2022 transfer fee mint from a transfer delivering `amount`,
+// mints without the transfer fee extension (including legacy token mints) charge none
+fn inverse_transfer_fee(mint_token: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint_token.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?),
+        Err(_) => Ok(0),
+    }
+}
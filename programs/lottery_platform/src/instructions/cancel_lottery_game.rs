@@ -0,0 +1,78 @@
+//! CancelLotteryGame instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::GameCancelled,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::LotteryGame,
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct CancelLotteryGame<'info> {
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.is_cancelled @ LotteryGameError::LotteryGameCancelled,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // only the current round can still be unsettled
+    #[account(mut,has_one = lottery_game,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_game.current_round.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn cancel_lottery_game(ctx: Context<CancelLotteryGame>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    // Unwind the current round unless its winners are already drawn, prizes of
+    // settled rounds stay claimable
+    match lottery_round.status {
+        LotteryRoundStatus::Open if lottery_round.tickets_sold == 0 => {
+            lottery_round.status = LotteryRoundStatus::Unwon;
+        }
+        LotteryRoundStatus::Open | LotteryRoundStatus::DrawPending => {
+            lottery_round.status = LotteryRoundStatus::Refundable;
+        }
+        // the draw has already split the round's pool into prize, fees, operator
+        // revenue and reserve, the tally has to finish first
+        LotteryRoundStatus::Tallying => {
+            return Err(LotteryGameError::LotteryRoundAlreadyDrawn.into());
+        }
+        LotteryRoundStatus::Closed | LotteryRoundStatus::Refundable | LotteryRoundStatus::Unwon => {
+        }
+    }
+
+    let clock = Clock::get()?;
+    lottery_game.is_cancelled = true;
+    lottery_game.cancelled_at = clock.unix_timestamp;
+
+    emit!(GameCancelled {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        round_status: lottery_round.status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! ClaimRefund instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::{RefundClaimed, RoundRefundable},
+        state::{
+            deposit_base::DepositBase,
+            game_entry::GameEntry,
+            lottery_game::{LotteryGame, PaymentMode},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+            participant::Participant,
+            ticket::Ticket,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(has_one = owner)]
+    pub participant: Account<'info, Participant>,
+    // the participant's entry in this lottery game
+    #[account(mut,has_one = owner, has_one = lottery_game, has_one = participant,
+        seeds = [b"game-entry", lottery_game.key().as_ref(), participant.key().as_ref()],
+        bump = game_entry.bump
+    )]
+    pub game_entry: Account<'info, GameEntry>,
+    // the refunded ticket, it must be owned by the caller
+    #[account(mut,has_one = owner, has_one = lottery_game,
+        constraint = ticket.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = ticket.round_number == lottery_round.round_number @ LotteryGameError::RoundNotRefundable,
+        constraint = !ticket.is_refunded @ LotteryGameError::TicketAlreadyRefunded,
+        seeds = [b"ticket", lottery_game.key().as_ref(), ticket.ticket_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == lottery_game.mint @ LotteryGameError::InvalidMint
+    )]
+    pub recipient_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut,
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associate_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    let game_entry = &mut ctx.accounts.game_entry;
+    let ticket = &mut ctx.accounts.ticket;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let mint_token = &ctx.accounts.mint_token;
+    let deposit_account = &ctx.accounts.deposit_account;
+    let pda_auth = &mut ctx.accounts.pda_auth;
+    let treasury_vault = &mut ctx.accounts.treasury_vault;
+    let token_program = &ctx.accounts.token_program;
+    // the ticket holder gets back exactly what they paid for the ticket
+    let _amount = ticket.amount_paid;
+
+    // a round that missed its minimum tickets becomes refundable once its sales close
+    let clock = Clock::get()?;
+    if lottery_round.missed_min_tickets(lottery_game, clock.unix_timestamp) {
+        lottery_round.status = LotteryRoundStatus::Refundable;
+        emit!(RoundRefundable::new(lottery_round, clock.unix_timestamp));
+    }
+
+    if lottery_round.status != LotteryRoundStatus::Refundable {
+        return Err(LotteryGameError::RoundNotRefundable.into());
+    }
+
+    if _amount == 0 {
+        return Err(LotteryGameError::InsufficientFunds.into());
+    }
+
+    // round's available funds should cover the refund
+    if lottery_round.available_funds < _amount {
+        return Err(LotteryGameError::InsufficientFunds.into());
+    }
+
+    ticket.is_refunded = true;
+
+    game_entry.amount_refunded = game_entry
+        .amount_refunded
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    lottery_round.available_funds = lottery_round
+        .available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.amounts_refunded = lottery_round
+        .amounts_refunded
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_amounts_refunded = lottery_game
+        .total_amounts_refunded
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(recipient_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (sender_tokens, recipient_tokens, mint_token, token_program)
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            // Transfer funds from treasury vault to recipient
+            deposit_account.transfer_tokens(
+                &pda_auth.to_account_info(),
+                &treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &recipient_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay the ticket holder lamports out of the treasury vault
+        PaymentMode::Sol => deposit_account.transfer_lamports(
+            &pda_auth.to_account_info(),
+            &treasury_vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            _amount,
+        )?,
+    }
+
+    emit!(RefundClaimed {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        ticket_number: ticket.ticket_number,
+        owner: *ctx.accounts.owner.key,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
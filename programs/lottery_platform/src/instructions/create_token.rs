@@ -1,22 +1,18 @@
 //! CreateToken instruction handler
 
 use {
-    crate::{error::LotteryGameError, state::lottery_game::LotteryGame},
+    crate::error::LotteryGameError,
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token,
         associated_token::AssociatedToken,
-        token::{initialize_mint, mint_to, InitializeMint, Mint, MintTo, Token, TokenAccount},
+        token::{initialize_mint, mint_to, InitializeMint, MintTo, Token},
     },
 };
 
 #[derive(Accounts)]
 #[instruction(params: CreateTokenParams)]
 pub struct CreateToken<'info> {
-    #[account(mut,
-        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized
-    )]
-    pub lottery_game: Account<'info, LotteryGame>,
     #[account(mut)]
     ///CHECK:
     pub token_account: AccountInfo<'info>,
@@ -33,7 +29,8 @@ pub struct CreateToken<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateTokenParams {
-    pub amount: u32,
+    pub amount: u64,  // amount to mint in base units
+    pub decimals: u8, // decimals for the token mint
 }
 
 pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Result<()> {
@@ -42,19 +39,12 @@ pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Re
         return Err(LotteryGameError::InvalidAmount.into());
     }
 
-    let lottery_game = &ctx.accounts.lottery_game;
-    let decimals = lottery_game.decimals;
-    let _amount = params.amount;
-
-    let base: u32 = 10;
-    let exponent = lottery_game.decimals as u32;
+    if params.decimals == 0 {
+        return Err(LotteryGameError::InvalidNumeric.into());
+    }
 
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
-        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    let decimals = params.decimals;
+    let _amount = params.amount;
 
     system_program::create_account(
         CpiContext::new(
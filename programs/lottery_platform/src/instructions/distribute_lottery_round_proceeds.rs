@@ -0,0 +1,156 @@
+//! DistributeLotteryRoundProceeds instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::RoundProceedsDistributed,
+        state::{
+            deposit_base::DepositBase,
+            lottery_game::{LotteryGame, PaymentMode},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct DistributeLotteryRoundProceeds<'info> {
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Closed @ LotteryGameError::LotteryRoundNotSettled,
+        constraint = !lottery_round.is_proceeds_distributed @ LotteryGameError::ProceedsAlreadyDistributed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.operator_revenue_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub operator_revenue_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.reserve_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub reserve_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // sol lottery games pay the proceeds to these wallets instead
+    #[account(mut, address = lottery_game.operator_revenue_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub operator_revenue_wallet: Option<SystemAccount<'info>>,
+    #[account(mut, address = lottery_game.reserve_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub reserve_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // anyone may route a settled round's proceeds to the configured accounts
+    pub signer: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn distribute_lottery_round_proceeds(
+    ctx: Context<DistributeLotteryRoundProceeds>,
+) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    let operator_revenue_amount = lottery_round.operator_revenue_amount;
+    let reserve_amount = lottery_round.reserve_amount;
+
+    let total_amount = operator_revenue_amount
+        .checked_add(reserve_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    // Deduct the distributed proceeds from total_available_funds
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(total_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_operator_revenue = lottery_game
+        .total_operator_revenue
+        .checked_add(operator_revenue_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_reserve = lottery_game
+        .total_reserve
+        .checked_add(reserve_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.is_proceeds_distributed = true;
+
+    for (amount, recipient_tokens, recipient_wallet) in [
+        (
+            operator_revenue_amount,
+            &ctx.accounts.operator_revenue_tokens,
+            &ctx.accounts.operator_revenue_wallet,
+        ),
+        (
+            reserve_amount,
+            &ctx.accounts.reserve_tokens,
+            &ctx.accounts.reserve_wallet,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let _amount = amount;
+
+        match (
+            lottery_game.payment_mode,
+            recipient_tokens,
+            recipient_wallet,
+        ) {
+            (PaymentMode::Token, Some(recipient_tokens), _) => {
+                let (Some(sender_tokens), Some(mint_token), Some(token_program)) = (
+                    &ctx.accounts.sender_tokens,
+                    &ctx.accounts.mint_token,
+                    &ctx.accounts.token_program,
+                ) else {
+                    return Err(LotteryGameError::InvalidPaymentMode.into());
+                };
+
+                ctx.accounts.deposit_account.transfer_tokens(
+                    &ctx.accounts.pda_auth.to_account_info(),
+                    &ctx.accounts.treasury_vault.to_account_info(),
+                    &sender_tokens.to_account_info(),
+                    &recipient_tokens.to_account_info(),
+                    mint_token,
+                    &token_program.to_account_info(),
+                    _amount,
+                )?;
+            }
+            // sol lottery games pay lamports out of the treasury vault
+            (PaymentMode::Sol, _, Some(recipient_wallet)) => {
+                ctx.accounts.deposit_account.transfer_lamports(
+                    &ctx.accounts.pda_auth.to_account_info(),
+                    &ctx.accounts.treasury_vault.to_account_info(),
+                    &recipient_wallet.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    _amount,
+                )?
+            }
+            _ => return Err(LotteryGameError::InvalidPaymentMode.into()),
+        }
+    }
+
+    let clock = Clock::get()?;
+    emit!(RoundProceedsDistributed {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        operator_revenue_amount,
+        reserve_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
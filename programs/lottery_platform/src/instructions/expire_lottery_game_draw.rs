@@ -0,0 +1,63 @@
+//! ExpireLotteryGameDraw instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::RoundRefundable,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::LotteryGame,
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct ExpireLotteryGameDraw<'info> {
+    // a paused draw can't be revealed, so it can't be expired either
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::DrawPending @ LotteryGameError::DrawNotPending,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    // anyone may expire a draw the operator failed to reveal in time
+    pub signer: Signer<'info>,
+}
+
+pub fn expire_lottery_game_draw(ctx: Context<ExpireLotteryGameDraw>) -> Result<()> {
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    let clock = Clock::get()?;
+    let reveal_deadline = lottery_round.draw_window_end(
+        &ctx.accounts.lottery_game,
+        &ctx.accounts.lottery_game_configs,
+    )?;
+
+    if clock.slot <= reveal_deadline {
+        return Err(LotteryGameError::RevealWindowOpen.into());
+    }
+
+    // the draw can no longer be settled fairly, participants are owed their tickets back
+    lottery_round.status = LotteryRoundStatus::Refundable;
+
+    emit!(RoundRefundable::new(lottery_round, clock.unix_timestamp));
+
+    Ok(())
+}
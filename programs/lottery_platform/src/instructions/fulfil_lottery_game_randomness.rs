@@ -0,0 +1,83 @@
+//! FulfilLotteryGameRandomness instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::WinnerDrawn,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::{DrawMethod, LotteryGame},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: FulfilLotteryGameRandomnessParams)]
+pub struct FulfilLotteryGameRandomness<'info> {
+    // draws are blocked while the platform is paused
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = lottery_game.draw_method == DrawMethod::Oracle @ LotteryGameError::InvalidDrawMethod,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::DrawPending @ LotteryGameError::DrawNotPending,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(
+        constraint = oracle_authority.key() == lottery_game.oracle_authority @ LotteryGameError::InvalidOracleAuthority
+    )]
+    pub oracle_authority: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FulfilLotteryGameRandomnessParams {
+    pub request_id: u64,      // id of the randomness request being fulfilled
+    pub randomness: [u8; 32], // randomness produced by the oracle
+}
+
+pub fn fulfil_lottery_game_randomness(
+    ctx: Context<FulfilLotteryGameRandomness>,
+    params: &FulfilLotteryGameRandomnessParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    if params.request_id != lottery_round.randomness_request_id {
+        return Err(LotteryGameError::InvalidRandomnessRequest.into());
+    }
+
+    let clock = Clock::get()?;
+    let fulfil_deadline = lottery_round.draw_window_end(
+        &ctx.accounts.lottery_game,
+        &ctx.accounts.lottery_game_configs,
+    )?;
+
+    if clock.slot > fulfil_deadline {
+        return Err(LotteryGameError::RevealWindowExpired.into());
+    }
+
+    lottery_round.settle_draw(&mut ctx.accounts.lottery_game, params.randomness)?;
+
+    // pick numbers rounds only know their winners once every ticket is tallied
+    if lottery_round.status == LotteryRoundStatus::Closed {
+        emit!(WinnerDrawn::new(lottery_round));
+    }
+
+    Ok(())
+}
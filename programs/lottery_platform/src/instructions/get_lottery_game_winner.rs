@@ -3,9 +3,13 @@
 use {
     crate::{
         error::LotteryGameError,
+        events::{DrawRequested, RoundRefundable},
         state::{
-            configs::LotteryGameConfigs, deposit_base::DepositBase, lottery_game::LotteryGame,
-            lottery_operator::LotteryOperator,
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{DrawMethod, LotteryGame},
+            lottery_operator::{LotteryOperator, OperatorStatus},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
         },
     },
     anchor_lang::prelude::*,
@@ -15,15 +19,36 @@ use {
 #[derive(Accounts)]
 #[instruction(params: GetLotteryGameWinnerParams)]
 pub struct GetLotteryGameWinner<'info> {
+    // only draw authorities can draw the winners
     #[account(
-        mut, constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized
+        mut, constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_draw_authority(owner.key) @ LotteryGameError::MissingDrawRole,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
     )]
     pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
-    #[account(mut,
+    #[account(has_one = owner,
         constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
-        constraint = !lottery_game.is_closed @ LotteryGameError::LotteryGameClosed
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = !lottery_game.is_cancelled @ LotteryGameError::LotteryGameCancelled,
+        constraint = lottery_game.draw_method == DrawMethod::CommitReveal @ LotteryGameError::InvalidDrawMethod,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
     )]
     pub lottery_game: Account<'info, LotteryGame>,
+    // suspended operators can't draw their lottery games
+    #[account(
+        address = lottery_game.operator @ LotteryGameError::InvalidOperator,
+        constraint = lottery_operator.status == OperatorStatus::Active @ LotteryGameError::OperatorSuspended
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Open @ LotteryGameError::LotteryGameClosed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
     #[account(mut,has_one = owner,
         constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
     )]
@@ -41,20 +66,12 @@ pub struct GetLotteryGameWinner<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetLotteryGameWinnerParams {
-    operator: LotteryOperator, // lottery operator details
     country: String,           // home country where lottery game is implemented
     lottery_game_name: String, // lottery game name
 }
 
-// operator length
-const OPERATOR_LENGTH: usize = 30;
 // lottery_game_name length
 const LOTTERY_GAME_NAME: usize = 20;
-// tenor length
-const TENOR_LENGTH: u8 = 2;
-const TENOR_LENGTH_2: u8 = 30;
-// date length
-const DATE_LENGTH: usize = 20;
 // country length
 const COUNTRY_LENGTH: usize = 3;
 const COUNTRY_LENGTH_2: usize = 2;
@@ -65,35 +82,17 @@ pub fn get_lottery_game_winner(
 ) -> Result<()> {
     // validate inputs
     msg!("Validate inputs");
-    if params.operator.operator.as_bytes().len() > 0
-        && params.operator.operator.as_bytes().len() <= OPERATOR_LENGTH
-    {
-    } else {
-        return Err(LotteryGameError::InvalidOperatorLength.into());
-    }
-
-    if params.country.as_bytes().len() != COUNTRY_LENGTH
-        && params.country.as_bytes().len() != COUNTRY_LENGTH_2
-    {
+    if params.country.len() != COUNTRY_LENGTH && params.country.len() != COUNTRY_LENGTH_2 {
         return Err(LotteryGameError::InvalidCountryLength.into());
     }
 
-    if params.lottery_game_name.as_bytes().len() > 0
-        && params.lottery_game_name.as_bytes().len() <= LOTTERY_GAME_NAME
-    {
+    if !params.lottery_game_name.is_empty() && params.lottery_game_name.len() <= LOTTERY_GAME_NAME {
     } else {
         return Err(LotteryGameError::InvalidLotteryGameNameLength.into());
     }
-    /*
-    if params.value_date.as_bytes().len() > 0 && params.value_date.as_bytes().len() <= DATE_LENGTH {
-    } else {
-        return Err(LotteryGameError::InvalidValueDateLength.into());
-    }
-    */
 
-    let deposit_account = &mut ctx.accounts.deposit_account;
-    let lottery_game = &mut ctx.accounts.lottery_game;
-    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+    let lottery_game = &ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
 
     if lottery_game.lottery_winning_percentage > 0 {
     } else {
@@ -105,25 +104,37 @@ pub fn get_lottery_game_winner(
         return Err(LotteryGameError::InvalidAmount.into());
     }
 
-    if lottery_game.decimals == 0 {
-        return Err(LotteryGameError::InvalidNumeric.into());
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < lottery_round.draw_at {
+        return Err(LotteryGameError::DrawTooEarly.into());
+    }
+
+    // too few tickets were sold, the round is refunded instead of drawn
+    if lottery_round.missed_min_tickets(lottery_game, clock.unix_timestamp) {
+        lottery_round.status = LotteryRoundStatus::Refundable;
+        emit!(RoundRefundable::new(lottery_round, clock.unix_timestamp));
+        return Ok(());
     }
 
-    if lottery_game.participants.is_empty() {
+    if lottery_round.tickets_sold == 0 {
         return Err(LotteryGameError::InvalidParticipants.into());
     }
 
-    // Get current slot (pseudo-randomness based on slot time)
-    let clock = Clock::get().unwrap();
-    let slot = clock.slot;
+    // Close ticket sales and lock in the slot whose successor's hash will be mixed
+    // with the operator's revealed seed. The winner is only derived on reveal.
+    lottery_round.draw_slot = clock.slot;
+    lottery_round.status = LotteryRoundStatus::DrawPending;
 
-    // Use slot number to derive a "random" index
-    let random_index: usize = (slot as usize) % lottery_game.participants.len();
-    // lottery game winner
-    lottery_game.lottery_game_winner_index = random_index as u32;
-    let lottery_game_winner: Pubkey = lottery_game.participants[random_index];
-    lottery_game.lottery_game_winner = lottery_game_winner;
-    lottery_game.is_closed = true;
+    emit!(DrawRequested {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        draw_method: lottery_game.draw_method,
+        draw_slot: lottery_round.draw_slot,
+        randomness_request_id: lottery_round.randomness_request_id,
+        tickets_sold: lottery_round.tickets_sold,
+        amounts_raised: lottery_round.amounts_raised,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
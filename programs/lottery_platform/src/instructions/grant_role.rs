@@ -0,0 +1,61 @@
+//! GrantRole instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::RoleGranted,
+        state::configs::{AdminRole, LotteryGameConfigs, MAX_AUTHORITIES},
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: GrantRoleParams)]
+pub struct GrantRole<'info> {
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    pub role: AdminRole,   // role being granted
+    pub authority: Pubkey, // signer the role is granted to
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, params: &GrantRoleParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.authority == Pubkey::default() {
+        return Err(LotteryGameError::InvalidAuthority.into());
+    }
+
+    let authorities = ctx.accounts.lottery_game_configs.authorities(params.role);
+
+    if authorities.contains(&params.authority) {
+        return Err(LotteryGameError::RoleAlreadyGranted.into());
+    }
+
+    if authorities.len() >= MAX_AUTHORITIES {
+        return Err(LotteryGameError::TooManyAuthorities.into());
+    }
+
+    authorities.push(params.authority);
+
+    let clock = Clock::get()?;
+    emit!(RoleGranted {
+        role: params.role,
+        authority: params.authority,
+        super_admin: *ctx.accounts.owner.key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 //! Init instruction handler
 
 use {
-    crate::{error::LotteryGameError, state::configs::LotteryGameConfigs},
+    crate::{
+        error::LotteryGameError, events::ConfigsInitialized, program::LotteryPlatform,
+        state::configs::LotteryGameConfigs,
+    },
     anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
 };
 
 #[derive(Accounts)]
@@ -18,6 +22,13 @@ pub struct Init<'info> {
         bump
     )]
     pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, LotteryPlatform>,
+    // only the program's upgrade authority can initialize the configs and become super admin
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(owner.key()) @ LotteryGameError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -29,6 +40,14 @@ pub fn init(ctx: Context<Init>) -> Result<()> {
 
     // lottery game
     lottery_game_configs.is_initialized = true;
+    // the super admin grants the operator and draw roles and sets the fee configuration
+    lottery_game_configs.super_admin = *ctx.accounts.owner.key;
+
+    let clock = Clock::get()?;
+    emit!(ConfigsInitialized {
+        super_admin: lottery_game_configs.super_admin,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,108 @@
+//! MigrateConfigs instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError, events::ConfigsMigrated,
+        instructions::migrate_lottery_game::LotteryOperatorV1, program::LotteryPlatform,
+        state::configs::LotteryGameConfigs,
+    },
+    anchor_lang::{
+        prelude::*,
+        system_program::{transfer, Transfer},
+        Discriminator,
+    },
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct MigrateConfigs<'info> {
+    // still in the original layout, so it can't be deserialized as LotteryGameConfigs
+    #[account(mut, owner = crate::ID, seeds = [b"lottery-game-configs"], bump)]
+    /// CHECK: discriminator and layout are checked in the handler.
+    pub lottery_game_configs: UncheckedAccount<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, LotteryPlatform>,
+    // only the program's upgrade authority can migrate the configs and become super admin
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(owner.key()) @ LotteryGameError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// original LotteryGameConfigs layout: a list of operator names, without roles or fees
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LotteryGameConfigsV1 {
+    #[max_len(5)]
+    pub operators: Vec<LotteryOperatorV1>,
+    pub is_initialized: bool,
+}
+
+pub fn migrate_configs(ctx: Context<MigrateConfigs>) -> Result<()> {
+    let lottery_game_configs = &ctx.accounts.lottery_game_configs;
+    let owner = &ctx.accounts.owner;
+
+    let legacy = {
+        let data = lottery_game_configs.try_borrow_data()?;
+
+        if data.len() < 8 || data[..8] != LotteryGameConfigs::DISCRIMINATOR {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        if data.len() == 8 + LotteryGameConfigs::INIT_SPACE {
+            return Err(LotteryGameError::AccountAlreadyMigrated.into());
+        }
+
+        if data.len() != 8 + LotteryGameConfigsV1::INIT_SPACE {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        LotteryGameConfigsV1::deserialize(&mut &data[8..])?
+    };
+
+    // legacy operators were bare names, they can't hold a role, the super admin grants
+    // the roles and registers the lottery operators again
+    let migrated = LotteryGameConfigs {
+        is_initialized: true,
+        super_admin: *owner.key,
+        ..Default::default()
+    };
+
+    // the wider layout needs more space, the upgrade authority tops up its rent
+    let space = 8 + LotteryGameConfigs::INIT_SPACE;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(lottery_game_configs.lamports());
+
+    if rent_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: owner.to_account_info(),
+                    to: lottery_game_configs.to_account_info(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    lottery_game_configs.realloc(space, true)?;
+    migrated.try_serialize(&mut &mut lottery_game_configs.try_borrow_mut_data()?[..])?;
+
+    let clock = Clock::get()?;
+    emit!(ConfigsMigrated {
+        super_admin: migrated.super_admin,
+        legacy_operators: legacy
+            .operators
+            .into_iter()
+            .map(|lottery_operator| lottery_operator.operator)
+            .collect(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,394 @@
+//! MigrateLotteryGame instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::GameMigrated,
+        state::{
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{
+                DrawMethod, GameMode, LotteryGame, PaymentMode, RolloverRule, BASIS_POINTS,
+            },
+            lottery_game_counter::LotteryGameCounter,
+            lottery_operator::{LotteryOperator, OperatorStatus},
+            lottery_round::{LotteryRound, LotteryRoundStatus, WinningTicket},
+            prize_tier::PrizeTier,
+        },
+    },
+    anchor_lang::{prelude::*, Discriminator},
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct MigrateLotteryGame<'info> {
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // the signer's lottery operator takes over the lottery game
+    #[account(mut,
+        constraint = lottery_operator.status == OperatorStatus::Active @ LotteryGameError::OperatorSuspended,
+        seeds = [b"lottery-operator", owner.key().as_ref()],
+        bump = lottery_operator.bump
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    // the migrated lottery game gets the admin's next game id
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + LotteryGameCounter::INIT_SPACE,
+        seeds = [b"lottery-game-counter", owner.key().as_ref()],
+        bump
+    )]
+    pub lottery_game_counter: Account<'info, LotteryGameCounter>,
+    // the admin's single lottery game of the original layout, closed once migrated
+    #[account(mut, owner = crate::ID, seeds = [b"lottery-game", owner.key().as_ref()], bump)]
+    /// CHECK: discriminator and layout are checked in the handler.
+    pub legacy_lottery_game: UncheckedAccount<'info>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LotteryGame::INIT_SPACE,
+        seeds = [b"lottery-game", owner.key().as_ref(), lottery_game_counter.next_game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // round number 0 records the legacy lottery game's single draw
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LotteryRound::INIT_SPACE,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    // the legacy lottery game's deposit account, its treasury vault carries on
+    #[account(has_one = owner,
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+    // the legacy lottery game's token mint, it was never recorded on the lottery game
+    pub mint_token: InterfaceAccount<'info, Mint>,
+    // legacy ticket proceeds, held in a token account of the treasury vault
+    #[account(mut,
+        token::mint = mint_token,
+        token::authority = treasury_vault,
+        token::token_program = token_program
+    )]
+    pub legacy_vault_tokens: InterfaceAccount<'info, TokenAccount>,
+    // ticket proceeds of the migrated lottery game's next rounds are held here
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint_token,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
+        seeds = [b"vault-tokens", lottery_game.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: InterfaceAccount<'info, TokenAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateLotteryGameParams {
+    operator_revenue_account: Pubkey, // token account receiving the operator's revenue
+    reserve_account: Pubkey,          // token account receiving the reserve
+    draw_method: DrawMethod,          // source of the draw randomness of the next rounds
+    oracle_authority: Pubkey,         // signer allowed to fulfil the draw randomness (oracle)
+}
+
+// lottery operator details embedded in the original LotteryGame layout
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LotteryOperatorV1 {
+    #[max_len(30)]
+    pub operator: String,
+}
+
+// original LotteryGame layout: a single draw among a list of participants, with
+// amounts in u32 whole units of the mint
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LotteryGameV1 {
+    pub owner: Pubkey,
+    pub operator: LotteryOperatorV1,
+    #[max_len(3)]
+    pub country: String,
+    #[max_len(20)]
+    pub lottery_game_name: String,
+    pub total_amounts_raised: u32,
+    pub total_amounts_withdrawn: u32,
+    pub total_available_funds: u32,
+    pub is_initialized: bool,
+    #[max_len(10)]
+    pub participants: Vec<Pubkey>,
+    pub lottery_game_winner: Pubkey,
+    pub lottery_game_winner_index: u32,
+    pub lottery_winning_percentage: u32,
+    pub unit_cost_of_lottery_ticket: u32,
+    pub decimals: u8,
+    #[max_len(20)]
+    pub value_date: String,
+    pub is_closed: bool,
+}
+
+// percentages of the original layout were whole percents
+const LEGACY_PERCENT: u32 = 100;
+
+// The legacy lottery game's outstanding funds are settled from its vault: the
+// drawn winner's unclaimed prize is paid out, or, when it was never drawn, every
+// ticket is refunded. The recipients' token accounts are passed as remaining
+// accounts, the winner's alone or one per legacy participant entry in order.
+pub fn migrate_lottery_game<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateLotteryGame<'info>>,
+    params: &MigrateLotteryGameParams,
+) -> Result<()> {
+    let legacy_lottery_game = &ctx.accounts.legacy_lottery_game;
+    let owner = &ctx.accounts.owner;
+
+    let legacy = {
+        let data = legacy_lottery_game.try_borrow_data()?;
+
+        if data.len() < 8 || data[..8] != LotteryGame::DISCRIMINATOR {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        if data.len() != 8 + LotteryGameV1::INIT_SPACE {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        LotteryGameV1::deserialize(&mut &data[8..])?
+    };
+
+    if legacy.owner != *owner.key || !legacy.is_initialized {
+        return Err(LotteryGameError::InvalidMigration.into());
+    }
+
+    if legacy.decimals != ctx.accounts.mint_token.decimals {
+        return Err(LotteryGameError::InvalidMint.into());
+    }
+
+    if legacy.lottery_winning_percentage == 0 || legacy.lottery_winning_percentage > LEGACY_PERCENT
+    {
+        return Err(LotteryGameError::InvalidMigration.into());
+    }
+
+    // lottery games can only be run in the countries the operator is licensed in
+    if !ctx
+        .accounts
+        .lottery_operator
+        .licensed_countries
+        .contains(&legacy.country)
+    {
+        return Err(LotteryGameError::CountryNotLicensed.into());
+    }
+
+    if params.operator_revenue_account == Pubkey::default()
+        || params.reserve_account == Pubkey::default()
+    {
+        return Err(LotteryGameError::InvalidRevenueAccount.into());
+    }
+
+    if params.draw_method == DrawMethod::Oracle && params.oracle_authority == Pubkey::default() {
+        return Err(LotteryGameError::InvalidOracleAuthority.into());
+    }
+
+    // whole units of the mint become base units
+    let scale = 10u64
+        .checked_pow(legacy.decimals as u32)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    let to_base_units = |amount: u32| {
+        (amount as u64)
+            .checked_mul(scale)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)
+    };
+
+    let unit_cost_of_lottery_ticket = to_base_units(legacy.unit_cost_of_lottery_ticket)?;
+    let amounts_raised = to_base_units(legacy.total_amounts_raised)?;
+    let amounts_withdrawn = to_base_units(legacy.total_amounts_withdrawn)?;
+    let tickets_sold = legacy.participants.len() as u64;
+
+    // a drawn lottery game owes its winner the funds left, an undrawn one owes
+    // every ticket holder the ticket price
+    let payouts: Vec<(Pubkey, u64)> = if legacy.is_closed {
+        match legacy.total_available_funds {
+            0 => Vec::new(),
+            available_funds => vec![(legacy.lottery_game_winner, to_base_units(available_funds)?)],
+        }
+    } else {
+        legacy
+            .participants
+            .iter()
+            .map(|participant| (*participant, unit_cost_of_lottery_ticket))
+            .collect()
+    };
+
+    if ctx.remaining_accounts.len() != payouts.len() {
+        return Err(LotteryGameError::InvalidParticipants.into());
+    }
+
+    let deposit_account = &ctx.accounts.deposit_account;
+    let mut amount_paid_out: u64 = 0;
+    for ((recipient, amount), account_info) in payouts.iter().zip(ctx.remaining_accounts.iter()) {
+        let recipient_tokens = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+
+        if recipient_tokens.mint != ctx.accounts.mint_token.key() {
+            return Err(LotteryGameError::InvalidMint.into());
+        }
+
+        if recipient_tokens.owner != *recipient {
+            return Err(LotteryGameError::InvalidParticipants.into());
+        }
+
+        deposit_account.transfer_tokens(
+            &ctx.accounts.pda_auth.to_account_info(),
+            &ctx.accounts.treasury_vault.to_account_info(),
+            &ctx.accounts.legacy_vault_tokens.to_account_info(),
+            &recipient_tokens.to_account_info(),
+            &ctx.accounts.mint_token,
+            &ctx.accounts.token_program.to_account_info(),
+            *amount,
+        )?;
+
+        amount_paid_out = amount_paid_out
+            .checked_add(*amount)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    }
+
+    let (winnings_paid, refunds_paid) = if legacy.is_closed {
+        (amount_paid_out, 0)
+    } else {
+        (0, amount_paid_out)
+    };
+
+    let lottery_game_counter = &mut ctx.accounts.lottery_game_counter;
+    if !lottery_game_counter.is_initialized {
+        lottery_game_counter.owner = *owner.key;
+        lottery_game_counter.is_initialized = true;
+    }
+    let game_id = lottery_game_counter.next_game_id;
+    lottery_game_counter.next_game_id = game_id
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    let total_amounts_withdrawn = amounts_withdrawn
+        .checked_add(winnings_paid)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    // the legacy game paid its single winner everything raised, i.e a single jackpot
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    lottery_game.owner = *owner.key;
+    lottery_game.game_id = game_id;
+    lottery_game.bump = ctx.bumps.lottery_game;
+    lottery_game.operator = ctx.accounts.lottery_operator.key();
+    lottery_game.country = legacy.country;
+    lottery_game.lottery_game_name = legacy.lottery_game_name;
+    lottery_game.total_amounts_raised = amounts_raised;
+    lottery_game.total_amounts_withdrawn = total_amounts_withdrawn;
+    lottery_game.total_available_funds = 0;
+    lottery_game.is_initialized = true;
+    lottery_game.total_amounts_refunded = refunds_paid;
+    lottery_game.tickets_sold = tickets_sold;
+    lottery_game.current_round = 0;
+    lottery_game.rollover_rule = RolloverRule::None;
+    lottery_game.lottery_winning_percentage = legacy
+        .lottery_winning_percentage
+        .checked_mul(BASIS_POINTS / LEGACY_PERCENT)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.prize_tiers = vec![PrizeTier {
+        winners: 1,
+        matches: 0,
+        prize_percentage: BASIS_POINTS,
+    }];
+    lottery_game.operator_revenue_account = params.operator_revenue_account;
+    lottery_game.reserve_account = params.reserve_account;
+    // no fees were charged on ticket sales
+    lottery_game.total_prize_pool_amounts = amounts_raised;
+    lottery_game.unit_cost_of_lottery_ticket = unit_cost_of_lottery_ticket;
+    lottery_game.payment_mode = PaymentMode::Token;
+    lottery_game.treasury_vault = ctx.accounts.treasury_vault.key();
+    lottery_game.mint = ctx.accounts.mint_token.key();
+    lottery_game.decimals = legacy.decimals;
+    lottery_game.value_date = legacy.value_date;
+    // purchases were limited to a single ticket
+    lottery_game.max_tickets_per_purchase = 1;
+    lottery_game.draw_method = params.draw_method;
+    lottery_game.game_mode = GameMode::Raffle;
+    lottery_game.oracle_authority = params.oracle_authority;
+
+    // lottery_round, settled so the admin can start the next round
+    let clock = Clock::get()?;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    lottery_round.lottery_game = lottery_game.key();
+    lottery_round.round_number = 0;
+    lottery_round.bump = ctx.bumps.lottery_round;
+    lottery_round.tickets_sold = tickets_sold;
+    lottery_round.amounts_raised = amounts_raised;
+    lottery_round.amounts_withdrawn = total_amounts_withdrawn;
+    lottery_round.amounts_refunded = refunds_paid;
+    lottery_round.is_proceeds_distributed = true;
+    lottery_round.is_initialized = true;
+
+    if legacy.is_closed {
+        lottery_round.status = LotteryRoundStatus::Closed;
+        lottery_round.winning_tickets = vec![WinningTicket {
+            ticket_number: legacy.lottery_game_winner_index as u64,
+            tier: 0,
+            prize_amount: total_amounts_withdrawn,
+            is_claimed: true,
+        }];
+        lottery_round.prize_amount = total_amounts_withdrawn;
+        lottery_round.drawn_at = clock.unix_timestamp;
+    } else if tickets_sold == 0 {
+        lottery_round.status = LotteryRoundStatus::Unwon;
+    } else {
+        lottery_round.status = LotteryRoundStatus::Refundable;
+    }
+
+    // lottery_operator
+    let lottery_operator = &mut ctx.accounts.lottery_operator;
+    lottery_operator.game_count = lottery_operator
+        .game_count
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    // the legacy lottery game's rent goes back to the admin
+    let legacy_info = legacy_lottery_game.to_account_info();
+    let owner_info = owner.to_account_info();
+    let legacy_lamports = legacy_info.lamports();
+    **owner_info.try_borrow_mut_lamports()? = owner_info
+        .lamports()
+        .checked_add(legacy_lamports)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    **legacy_info.try_borrow_mut_lamports()? = 0;
+    legacy_info.assign(&System::id());
+    legacy_info.realloc(0, false)?;
+
+    emit!(GameMigrated {
+        lottery_game: lottery_game.key(),
+        legacy_lottery_game: legacy_info.key(),
+        owner: lottery_game.owner,
+        game_id,
+        operator: lottery_game.operator,
+        winnings_paid,
+        refunds_paid,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
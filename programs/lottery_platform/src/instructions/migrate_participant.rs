@@ -0,0 +1,182 @@
+//! MigrateParticipant instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::ParticipantMigrated,
+        instructions::migrate_lottery_game::LotteryGameV1,
+        state::{lottery_game::LotteryGame, participant::Participant},
+    },
+    anchor_lang::{
+        prelude::*,
+        system_program::{transfer, Transfer},
+        Discriminator,
+    },
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct MigrateParticipant<'info> {
+    // still in the u32 whole units layout, so it can't be deserialized as a Participant
+    #[account(mut, owner = crate::ID, seeds = [b"participant", owner.key().as_ref()], bump)]
+    /// CHECK: discriminator and layout are checked in the handler.
+    pub participant: UncheckedAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Participant layout in which amounts were u32 whole units of the mint
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct ParticipantV1 {
+    pub owner: Pubkey,
+    #[max_len(50)]
+    pub full_names: String,
+    #[max_len(3)]
+    pub country: String,
+    pub active: bool,
+    pub total_units_lottery_ticket: u32,
+    pub available_funds: u32,
+}
+
+// The legacy participant doesn't record the lottery games it bought tickets in,
+// they record it instead: every ticket bought pushed the buyer onto the game's
+// participants. The original lottery games the participant bought tickets in are
+// passed as remaining accounts, before they are migrated themselves, and the
+// participant's tickets and funds are rebuilt from them.
+pub fn migrate_participant(ctx: Context<MigrateParticipant>) -> Result<()> {
+    let participant = &ctx.accounts.participant;
+    let owner = &ctx.accounts.owner;
+
+    let legacy = {
+        let data = participant.try_borrow_data()?;
+
+        if data.len() < 8 || data[..8] != Participant::DISCRIMINATOR {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        if data.len() == 8 + Participant::INIT_SPACE {
+            return Err(LotteryGameError::AccountAlreadyMigrated.into());
+        }
+
+        if data.len() != 8 + ParticipantV1::INIT_SPACE {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        ParticipantV1::deserialize(&mut &data[8..])?
+    };
+
+    // tickets bought in each legacy lottery game, with their ticket price in whole
+    // and in base units of the lottery game's mint
+    let mut tickets: u64 = 0;
+    let mut units_lottery_ticket: u64 = 0;
+    let mut available_funds: u64 = 0;
+    let mut total_amount_spent: u64 = 0;
+    let mut legacy_lottery_games: Vec<Pubkey> = Vec::new();
+    for account_info in ctx.remaining_accounts.iter() {
+        if account_info.owner != &crate::ID || legacy_lottery_games.contains(account_info.key) {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+        legacy_lottery_games.push(*account_info.key);
+
+        let legacy_lottery_game = {
+            let data = account_info.try_borrow_data()?;
+
+            if data.len() != 8 + LotteryGameV1::INIT_SPACE
+                || data[..8] != LotteryGame::DISCRIMINATOR
+            {
+                return Err(LotteryGameError::InvalidMigration.into());
+            }
+
+            LotteryGameV1::deserialize(&mut &data[8..])?
+        };
+
+        let game_tickets = legacy_lottery_game
+            .participants
+            .iter()
+            .filter(|participant| **participant == legacy.owner)
+            .count() as u64;
+
+        if game_tickets == 0 {
+            return Err(LotteryGameError::InvalidMigration.into());
+        }
+
+        // legacy units were recorded as unit_cost * amount, i.e the square of the whole
+        // units ticket price for every ticket bought
+        let unit_cost = legacy_lottery_game.unit_cost_of_lottery_ticket as u64;
+        let scale = 10u64
+            .checked_pow(legacy_lottery_game.decimals as u32)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        let game_funds = game_tickets
+            .checked_mul(unit_cost)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        tickets = tickets
+            .checked_add(game_tickets)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        units_lottery_ticket = game_funds
+            .checked_mul(unit_cost)
+            .and_then(|units| units.checked_add(units_lottery_ticket))
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        available_funds = available_funds
+            .checked_add(game_funds)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        total_amount_spent = game_funds
+            .checked_mul(scale)
+            .and_then(|amount| amount.checked_add(total_amount_spent))
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    }
+
+    // every legacy ticket and unit of funds must be accounted for by the lottery
+    // games passed, none of them may be left behind or made up
+    if units_lottery_ticket != legacy.total_units_lottery_ticket as u64
+        || available_funds != legacy.available_funds as u64
+    {
+        return Err(LotteryGameError::InvalidMigration.into());
+    }
+
+    // the legacy available funds are held by those lottery games, which refund
+    // them (or pay them out to their winner) when they are migrated
+    let migrated = Participant {
+        owner: legacy.owner,
+        full_names: legacy.full_names,
+        country: legacy.country,
+        active: legacy.active,
+        total_units_lottery_ticket: tickets,
+        total_amount_spent,
+    };
+
+    // the wider layout needs more space, the participant tops up its rent
+    let space = 8 + Participant::INIT_SPACE;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(participant.lamports());
+
+    if rent_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: owner.to_account_info(),
+                    to: participant.to_account_info(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    participant.realloc(space, true)?;
+    migrated.try_serialize(&mut &mut participant.try_borrow_mut_data()?[..])?;
+
+    let clock = Clock::get()?;
+    emit!(ParticipantMigrated {
+        participant: participant.key(),
+        owner: *owner.key,
+        total_units_lottery_ticket: migrated.total_units_lottery_ticket,
+        total_amount_spent: migrated.total_amount_spent,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! ProposeSuperAdmin instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError, events::SuperAdminProposed, state::configs::LotteryGameConfigs,
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: ProposeSuperAdminParams)]
+pub struct ProposeSuperAdmin<'info> {
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeSuperAdminParams {
+    pub super_admin: Pubkey, // proposed super admin, the default key withdraws a proposal
+}
+
+pub fn propose_super_admin(
+    ctx: Context<ProposeSuperAdmin>,
+    params: &ProposeSuperAdminParams,
+) -> Result<()> {
+    // the super admin only changes once the proposed one accepts, so a mistyped key
+    // can't lock the configs
+    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+    lottery_game_configs.pending_super_admin = params.super_admin;
+
+    let clock = Clock::get()?;
+    emit!(SuperAdminProposed {
+        super_admin: lottery_game_configs.super_admin,
+        pending_super_admin: params.super_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
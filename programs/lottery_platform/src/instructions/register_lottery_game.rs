@@ -3,21 +3,64 @@
 use {
     crate::{
         error::LotteryGameError,
+        events::GameRegistered,
         state::{
-            configs::LotteryGameConfigs, deposit_base::DepositBase, lottery_game::LotteryGame,
-            lottery_operator::LotteryOperator,
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{
+                DrawMethod, GameMode, LotteryGame, PaymentMode, RolloverRule, BASIS_POINTS,
+                MAX_PICK_COUNT, SOL_DECIMALS,
+            },
+            lottery_game_counter::LotteryGameCounter,
+            lottery_operator::{LotteryOperator, OperatorStatus},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+            prize_tier::{PrizeTier, MAX_PRIZE_TIERS, MAX_WINNERS},
         },
     },
-    anchor_lang::prelude::*,
+    anchor_lang::{
+        prelude::*,
+        system_program::{transfer, Transfer},
+    },
+    anchor_spl::token_interface::{
+        spl_token_2022::{
+            extension::{
+                default_account_state::DefaultAccountState, BaseStateWithExtensions, ExtensionType,
+                StateWithExtensions,
+            },
+            state::{AccountState, Mint as MintState},
+        },
+        Mint, TokenAccount, TokenInterface,
+    },
+    solana_program::sysvar::clock::Clock,
 };
 
 #[derive(Accounts)]
 #[instruction(params: RegisterLotteryGameParams)]
 pub struct RegisterLotteryGame<'info> {
+    // only operator authorities can register lottery games
     #[account(
-        mut, constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
     )]
     pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // the signer's lottery operator, suspended operators can't register lottery games
+    #[account(mut,
+        constraint = lottery_operator.status == OperatorStatus::Active @ LotteryGameError::OperatorSuspended,
+        seeds = [b"lottery-operator", owner.key().as_ref()],
+        bump = lottery_operator.bump
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    // created on the admin's first lottery game, hands out game ids from then on
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + LotteryGameCounter::INIT_SPACE,
+        seeds = [b"lottery-game-counter", owner.key().as_ref()],
+        bump
+    )]
+    pub lottery_game_counter: Account<'info, LotteryGameCounter>,
     // init means to create account
     // bump to use unique address for account
     #[account(
@@ -25,10 +68,19 @@ pub struct RegisterLotteryGame<'info> {
         payer = owner,
         space = 8 + LotteryGame::INIT_SPACE,
         constraint = !lottery_game.is_initialized @ LotteryGameError::AccountAlreadyInitialized,
-        seeds = [b"lottery-game", owner.key().as_ref()],
+        seeds = [b"lottery-game", owner.key().as_ref(), lottery_game_counter.next_game_id.to_le_bytes().as_ref()],
         bump
     )]
     pub lottery_game: Account<'info, LotteryGame>,
+    // first round of the lottery game, i.e round number 0
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LotteryRound::INIT_SPACE,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
     #[account(init, payer = owner, space = 8 + DepositBase::INIT_SPACE,
         constraint = !deposit_account.is_initialized @ LotteryGameError::AccountAlreadyInitialized
     )]
@@ -38,65 +90,215 @@ pub struct RegisterLotteryGame<'info> {
     pub pda_auth: UncheckedAccount<'info>,
     #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
     pub treasury_vault: SystemAccount<'info>,
+    // token mint accepted for the lottery game's tickets, legacy token or Token-2022,
+    // omitted along with the vault token account by sol lottery games
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // ticket proceeds are held here, only the treasury vault can move them
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint_token,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
+        seeds = [b"vault-tokens", lottery_game.key().as_ref()],
+        bump
+    )]
+    pub vault_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RegisterLotteryGameParams {
-    operator: LotteryOperator,        // lottery operator details
-    country: String,                  // home country where lottery game is implemented
-    lottery_game_name: String,        // lottery game name
-    lottery_winning_percentage: u32,  // lottery winning percentage
-    unit_cost_of_lottery_ticket: u32, // unit cost of lottery ticket
-    decimals: u8,                     // decimals for the token mint
-    value_date: String,               // value date of lottery draw
+    country: String,                 // home country where lottery game is implemented
+    lottery_game_name: String,       // lottery game name
+    lottery_winning_percentage: u32, // share of a round's ticket sales paid to the winner (basis points)
+    reserve_percentage: u32, // share of the non-prize remainder kept as reserve (basis points)
+    operator_fee_percentage: Option<u32>, // share of every ticket sale charged as the operator fee, defaults to the platform's (basis points)
+    prize_tiers: Vec<PrizeTier>, // prize table, tiers are drawn in order starting with the jackpot
+    operator_revenue_account: Pubkey, // token account receiving the operator's revenue
+    reserve_account: Pubkey,     // token account receiving the reserve
+    unit_cost_of_lottery_ticket: u64, // unit cost of lottery ticket (base units)
+    payment_mode: PaymentMode,   // currency the lottery game's tickets are paid in
+    value_date: String,          // value date of lottery draw
+    sales_open_at: i64, // unix timestamp from which the first round's tickets can be bought
+    sales_close_at: i64, // unix timestamp from which the first round's tickets can no longer be bought
+    draw_at: i64,        // unix timestamp from which the first round can be drawn
+    min_tickets: u64,    // tickets a round has to sell to be drawn, 0 means no minimum
+    max_tickets_per_purchase: u64, // tickets a single purchase can buy, 0 means no limit
+    max_tickets_per_game: u64, // tickets the lottery game can sell over all its rounds, 0 means no limit
+    draw_method: DrawMethod,   // source of the draw randomness
+    game_mode: GameMode,       // how the round winners are determined
+    pick_count: u8,            // numbers picked per ticket, e.g 6 in a 6/49 game (pick numbers)
+    number_range: u8,          // numbers are picked from 1 up to this number, e.g 49 (pick numbers)
+    draw_commitment: [u8; 32], // sha256 hash of the operator's secret draw seed (commit-reveal)
+    oracle_authority: Pubkey,  // signer allowed to fulfil the draw randomness (oracle)
+    rollover_rule: RolloverRule, // what happens to a round's leftover pool
+    claim_period: i64,         // seconds a round winner has to claim before the prize may roll over
+    claim_deadline: i64, // seconds after a round's draw its prizes can be claimed, 0 means no deadline
+    unclaimed_prizes_beneficiary: Pubkey, // token account (or wallet, sol) receiving prizes unclaimed by the deadline
 }
 
-// operator length
-const OPERATOR_LENGTH: usize = 30;
 // lottery_game_name length
 const LOTTERY_GAME_NAME: usize = 20;
-// tenor length
-const TENOR_LENGTH: u8 = 2;
-const TENOR_LENGTH_2: u8 = 30;
 // date length
 const DATE_LENGTH: usize = 20;
 // country length
 const COUNTRY_LENGTH: usize = 3;
 const COUNTRY_LENGTH_2: usize = 2;
 
+// Token-2022 extensions that let someone other than the vault move or freeze its
+// tokens, or stop them from being transferred at all
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+];
+
+// the vault and the winners' token accounts must be usable as soon as they're created
+fn check_mint_extensions(mint_token: &AccountInfo) -> Result<()> {
+    let data = mint_token.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    let extension_types = mint.get_extension_types()?;
+
+    if extension_types
+        .iter()
+        .any(|extension_type| UNSUPPORTED_MINT_EXTENSIONS.contains(extension_type))
+    {
+        return Err(LotteryGameError::UnsupportedMintExtension.into());
+    }
+
+    if let Ok(default_account_state) = mint.get_extension::<DefaultAccountState>() {
+        if default_account_state.state == AccountState::Frozen as u8 {
+            return Err(LotteryGameError::UnsupportedMintExtension.into());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn register_lottery_game(
     ctx: Context<RegisterLotteryGame>,
     params: &RegisterLotteryGameParams,
 ) -> Result<()> {
     // validate inputs
     msg!("Validate inputs");
-    if params.operator.operator.as_bytes().len() > 0
-        && params.operator.operator.as_bytes().len() <= OPERATOR_LENGTH
-    {
-    } else {
-        return Err(LotteryGameError::InvalidOperatorLength.into());
+    if params.country.len() != COUNTRY_LENGTH && params.country.len() != COUNTRY_LENGTH_2 {
+        return Err(LotteryGameError::InvalidCountryLength.into());
     }
 
-    if params.country.as_bytes().len() != COUNTRY_LENGTH
-        && params.country.as_bytes().len() != COUNTRY_LENGTH_2
+    // lottery games can only be run in the countries the operator is licensed in
+    if !ctx
+        .accounts
+        .lottery_operator
+        .licensed_countries
+        .contains(&params.country)
     {
-        return Err(LotteryGameError::InvalidCountryLength.into());
+        return Err(LotteryGameError::CountryNotLicensed.into());
     }
 
-    if params.lottery_game_name.as_bytes().len() > 0
-        && params.lottery_game_name.as_bytes().len() <= LOTTERY_GAME_NAME
-    {
+    if !params.lottery_game_name.is_empty() && params.lottery_game_name.len() <= LOTTERY_GAME_NAME {
     } else {
         return Err(LotteryGameError::InvalidLotteryGameNameLength.into());
     }
 
-    if params.lottery_winning_percentage > 0 {
+    if params.lottery_winning_percentage > 0 && params.lottery_winning_percentage <= BASIS_POINTS {
     } else {
-        return Err(LotteryGameError::InvalidAmount.into());
+        return Err(LotteryGameError::InvalidPercentage.into());
+    }
+
+    if params.reserve_percentage > BASIS_POINTS {
+        return Err(LotteryGameError::InvalidPercentage.into());
+    }
+
+    // the operator fee defaults to the platform's, the protocol fee is always the platform's
+    let lottery_game_configs = &ctx.accounts.lottery_game_configs;
+    let operator_fee_percentage = params
+        .operator_fee_percentage
+        .unwrap_or(lottery_game_configs.operator_fee_percentage);
+    let total_fee_percentage = operator_fee_percentage
+        .checked_add(lottery_game_configs.protocol_fee_percentage)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    if total_fee_percentage > BASIS_POINTS {
+        return Err(LotteryGameError::InvalidPercentage.into());
+    }
+
+    if !params.prize_tiers.is_empty() && params.prize_tiers.len() <= MAX_PRIZE_TIERS {
+    } else {
+        return Err(LotteryGameError::InvalidPrizeTiers.into());
+    }
+
+    match params.game_mode {
+        GameMode::Raffle => {
+            if params.pick_count != 0 || params.number_range != 0 {
+                return Err(LotteryGameError::InvalidNumberRange.into());
+            }
+        }
+        GameMode::PickNumbers => {
+            if params.pick_count > 0
+                && params.pick_count as usize <= MAX_PICK_COUNT
+                && params.pick_count <= params.number_range
+            {
+            } else {
+                return Err(LotteryGameError::InvalidNumberRange.into());
+            }
+        }
+    }
+
+    // every tier needs a share and the shares can't exceed the whole prize pool, raffle
+    // tiers are won by a number of drawn tickets, pick numbers tiers by a number of matches
+    let mut total_winners: usize = 0;
+    let mut total_prize_percentage: u32 = 0;
+    for (index, prize_tier) in params.prize_tiers.iter().enumerate() {
+        if prize_tier.prize_percentage == 0 {
+            return Err(LotteryGameError::InvalidPrizeTiers.into());
+        }
+
+        match params.game_mode {
+            GameMode::Raffle => {
+                if prize_tier.winners == 0 || prize_tier.matches != 0 {
+                    return Err(LotteryGameError::InvalidPrizeTiers.into());
+                }
+            }
+            GameMode::PickNumbers => {
+                let is_duplicate = params.prize_tiers[..index]
+                    .iter()
+                    .any(|other_tier| other_tier.matches == prize_tier.matches);
+
+                if prize_tier.winners != 0
+                    || prize_tier.matches == 0
+                    || prize_tier.matches > params.pick_count
+                    || is_duplicate
+                {
+                    return Err(LotteryGameError::InvalidPrizeTiers.into());
+                }
+            }
+        }
+
+        total_winners = total_winners
+            .checked_add(prize_tier.winners as usize)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        total_prize_percentage = total_prize_percentage
+            .checked_add(prize_tier.prize_percentage)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    }
+
+    if total_winners > MAX_WINNERS {
+        return Err(LotteryGameError::InvalidPrizeTiers.into());
+    }
+
+    if total_prize_percentage > BASIS_POINTS {
+        return Err(LotteryGameError::InvalidPercentage.into());
+    }
+
+    if params.operator_revenue_account == Pubkey::default()
+        || params.reserve_account == Pubkey::default()
+    {
+        return Err(LotteryGameError::InvalidRevenueAccount.into());
     }
 
     if params.unit_cost_of_lottery_ticket > 0 {
@@ -104,18 +306,95 @@ pub fn register_lottery_game(
         return Err(LotteryGameError::InvalidAmount.into());
     }
 
-    if params.value_date.as_bytes().len() > 0 && params.value_date.as_bytes().len() <= DATE_LENGTH {
+    if !params.value_date.is_empty() && params.value_date.len() <= DATE_LENGTH {
     } else {
         return Err(LotteryGameError::InvalidValueDateLength.into());
     }
 
-    if params.decimals == 0 {
+    // amounts are kept in base units of the mint, or lamports of sol
+    let (mint, decimals) = match (
+        params.payment_mode,
+        &ctx.accounts.mint_token,
+        &ctx.accounts.vault_tokens,
+    ) {
+        (PaymentMode::Token, Some(mint_token), Some(_)) => {
+            check_mint_extensions(&mint_token.to_account_info())?;
+            (mint_token.key(), mint_token.decimals)
+        }
+        (PaymentMode::Sol, None, None) => (Pubkey::default(), SOL_DECIMALS),
+        _ => return Err(LotteryGameError::InvalidPaymentMode.into()),
+    };
+
+    if params.claim_period < 0 || params.claim_deadline < 0 {
         return Err(LotteryGameError::InvalidNumeric.into());
     }
 
+    // rolling over unclaimed prizes is permissionless, winners need time to claim first
+    if params.rollover_rule == RolloverRule::UnwonAndUnclaimed && params.claim_period == 0 {
+        return Err(LotteryGameError::InvalidClaimPeriod.into());
+    }
+
+    // prizes still claimable until the deadline can't roll over before it
+    if params.rollover_rule == RolloverRule::UnwonAndUnclaimed
+        && params.claim_deadline > 0
+        && params.claim_period < params.claim_deadline
+    {
+        return Err(LotteryGameError::InvalidClaimDeadline.into());
+    }
+
+    // unclaimed prizes need somewhere to go once the claim deadline passes
+    if params.claim_deadline > 0 && params.unclaimed_prizes_beneficiary == Pubkey::default() {
+        return Err(LotteryGameError::InvalidBeneficiaryAccount.into());
+    }
+
+    match params.draw_method {
+        DrawMethod::CommitReveal => {
+            if params.draw_commitment == [0u8; 32] {
+                return Err(LotteryGameError::InvalidDrawCommitment.into());
+            }
+        }
+        DrawMethod::Oracle => {
+            if params.oracle_authority == Pubkey::default() {
+                return Err(LotteryGameError::InvalidOracleAuthority.into());
+            }
+        }
+    }
+
+    // sol lottery games hold the ticket proceeds in the treasury vault, which is
+    // funded up to the rent-exempt minimum so payouts can never leave it below
+    if params.payment_mode == PaymentMode::Sol {
+        let rent_exempt_shortfall = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(ctx.accounts.treasury_vault.lamports());
+
+        if rent_exempt_shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.treasury_vault.to_account_info(),
+                    },
+                ),
+                rent_exempt_shortfall,
+            )?;
+        }
+    }
+
     let deposit_account = &mut ctx.accounts.deposit_account;
     let lottery_game = &mut ctx.accounts.lottery_game;
-    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+    let lottery_operator = &mut ctx.accounts.lottery_operator;
+    let lottery_game_counter = &mut ctx.accounts.lottery_game_counter;
+
+    // lottery_game_counter
+    if !lottery_game_counter.is_initialized {
+        lottery_game_counter.owner = *ctx.accounts.owner.key;
+        lottery_game_counter.is_initialized = true;
+    }
+    let game_id = lottery_game_counter.next_game_id;
+    lottery_game_counter.next_game_id = game_id
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
     // deposit account
     // * - means dereferencing
@@ -126,22 +405,76 @@ pub fn register_lottery_game(
 
     // lottery_game
     lottery_game.owner = *ctx.accounts.owner.key;
-    lottery_game.operator.operator = params.operator.operator.to_string();
+    lottery_game.game_id = game_id;
+    lottery_game.bump = ctx.bumps.lottery_game;
+    lottery_game.operator = lottery_operator.key();
     lottery_game.country = params.country.to_string();
     lottery_game.lottery_game_name = params.lottery_game_name.to_string();
     lottery_game.is_initialized = true;
-    lottery_game.is_closed = false;
     lottery_game.lottery_winning_percentage = params.lottery_winning_percentage;
+    lottery_game.reserve_percentage = params.reserve_percentage;
+    lottery_game.prize_tiers = params.prize_tiers.clone();
+    lottery_game.operator_revenue_account = params.operator_revenue_account;
+    lottery_game.reserve_account = params.reserve_account;
+    lottery_game.operator_fee_percentage = operator_fee_percentage;
+    lottery_game.protocol_fee_percentage = lottery_game_configs.protocol_fee_percentage;
+    lottery_game.protocol_fee_account = lottery_game_configs.protocol_fee_account;
     lottery_game.unit_cost_of_lottery_ticket = params.unit_cost_of_lottery_ticket;
-    lottery_game.decimals = params.decimals;
+    lottery_game.payment_mode = params.payment_mode;
+    lottery_game.treasury_vault = ctx.accounts.treasury_vault.key();
+    lottery_game.mint = mint;
+    lottery_game.decimals = decimals;
     lottery_game.value_date = params.value_date.to_string();
+    lottery_game.min_tickets = params.min_tickets;
+    lottery_game.max_tickets_per_purchase = params.max_tickets_per_purchase;
+    lottery_game.max_tickets_per_game = params.max_tickets_per_game;
+    lottery_game.draw_method = params.draw_method;
+    lottery_game.game_mode = params.game_mode;
+    lottery_game.pick_count = params.pick_count;
+    lottery_game.number_range = params.number_range;
+    lottery_game.oracle_authority = params.oracle_authority;
+    lottery_game.rollover_rule = params.rollover_rule;
+    lottery_game.claim_period = params.claim_period;
+    lottery_game.claim_deadline = params.claim_deadline;
+    lottery_game.unclaimed_prizes_beneficiary = params.unclaimed_prizes_beneficiary;
+    lottery_game.current_round = 0;
 
-    let lottery_operator = LotteryOperator {
-        operator: params.operator.operator.to_string(),
-    };
+    // lottery_round
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    lottery_round.lottery_game = lottery_game.key();
+    lottery_round.round_number = 0;
+    lottery_round.bump = ctx.bumps.lottery_round;
+    lottery_round.status = LotteryRoundStatus::Open;
+    lottery_round.schedule(params.sales_open_at, params.sales_close_at, params.draw_at)?;
+    lottery_round.draw_commitment = params.draw_commitment;
+    lottery_round.is_initialized = true;
+
+    // lottery_operator
+    lottery_operator.game_count = lottery_operator
+        .game_count
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    // lottery_game_configs
-    lottery_game_configs.operators.push(lottery_operator);
+    let clock = Clock::get()?;
+    emit!(GameRegistered {
+        lottery_game: lottery_game.key(),
+        owner: lottery_game.owner,
+        game_id,
+        operator: lottery_game.operator,
+        country: lottery_game.country.to_string(),
+        lottery_game_name: lottery_game.lottery_game_name.to_string(),
+        payment_mode: lottery_game.payment_mode,
+        mint: lottery_game.mint,
+        unit_cost_of_lottery_ticket: lottery_game.unit_cost_of_lottery_ticket,
+        operator_fee_percentage: lottery_game.operator_fee_percentage,
+        protocol_fee_percentage: lottery_game.protocol_fee_percentage,
+        draw_method: lottery_game.draw_method,
+        game_mode: lottery_game.game_mode,
+        sales_open_at: lottery_round.sales_open_at,
+        sales_close_at: lottery_round.sales_close_at,
+        draw_at: lottery_round.draw_at,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
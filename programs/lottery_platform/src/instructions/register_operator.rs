@@ -0,0 +1,104 @@
+//! RegisterOperator instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::OperatorRegistered,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_operator::{LotteryOperator, OperatorStatus, MAX_LICENSED_COUNTRIES},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: RegisterOperatorParams)]
+pub struct RegisterOperator<'info> {
+    // only the super admin can license operators
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LotteryOperator::INIT_SPACE,
+        constraint = !lottery_operator.is_initialized @ LotteryGameError::AccountAlreadyInitialized,
+        seeds = [b"lottery-operator", params.authority.as_ref()],
+        bump
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterOperatorParams {
+    pub authority: Pubkey, // signer registering and running the operator's lottery games
+    pub name: String,      // company operating the lottery
+    pub licensed_countries: Vec<String>, // countries the operator can run lottery games in
+}
+
+// operator length
+const OPERATOR_LENGTH: usize = 30;
+// country length
+const COUNTRY_LENGTH: usize = 3;
+const COUNTRY_LENGTH_2: usize = 2;
+
+pub fn register_operator(
+    ctx: Context<RegisterOperator>,
+    params: &RegisterOperatorParams,
+) -> Result<()> {
+    // validate inputs
+    msg!("Validate inputs");
+    if params.authority == Pubkey::default() {
+        return Err(LotteryGameError::InvalidAuthority.into());
+    }
+
+    if !params.name.is_empty() && params.name.len() <= OPERATOR_LENGTH {
+    } else {
+        return Err(LotteryGameError::InvalidOperatorLength.into());
+    }
+
+    if !params.licensed_countries.is_empty()
+        && params.licensed_countries.len() <= MAX_LICENSED_COUNTRIES
+    {
+    } else {
+        return Err(LotteryGameError::InvalidLicensedCountries.into());
+    }
+
+    for country in params.licensed_countries.iter() {
+        if country.len() != COUNTRY_LENGTH && country.len() != COUNTRY_LENGTH_2 {
+            return Err(LotteryGameError::InvalidCountryLength.into());
+        }
+    }
+
+    let lottery_operator = &mut ctx.accounts.lottery_operator;
+    lottery_operator.authority = params.authority;
+    lottery_operator.bump = ctx.bumps.lottery_operator;
+    lottery_operator.name = params.name.to_string();
+    lottery_operator.licensed_countries = params.licensed_countries.clone();
+    lottery_operator.status = OperatorStatus::Active;
+    lottery_operator.game_count = 0;
+    lottery_operator.is_initialized = true;
+
+    let clock = Clock::get()?;
+    emit!(OperatorRegistered {
+        lottery_operator: lottery_operator.key(),
+        authority: params.authority,
+        name: params.name.to_string(),
+        licensed_countries: params.licensed_countries.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 //! RegisterParticipant instruction handler
 
 use {
-    crate::{error::LotteryGameError, state::participant::Participant},
+    crate::{
+        error::LotteryGameError, events::ParticipantRegistered, state::participant::Participant,
+    },
     anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
 };
 
 #[derive(Accounts)]
@@ -42,16 +45,12 @@ pub fn register_participant(
 ) -> Result<()> {
     // validate inputs
     msg!("Validate inputs");
-    if params.full_names.as_bytes().len() > 0
-        && params.full_names.as_bytes().len() <= FULL_NAMES_LENGTH
-    {
+    if !params.full_names.is_empty() && params.full_names.len() <= FULL_NAMES_LENGTH {
     } else {
         return Err(LotteryGameError::InvalidFullNamesLength.into());
     }
 
-    if params.country.as_bytes().len() != COUNTRY_LENGTH
-        && params.country.as_bytes().len() != COUNTRY_LENGTH_2
-    {
+    if params.country.len() != COUNTRY_LENGTH && params.country.len() != COUNTRY_LENGTH_2 {
         return Err(LotteryGameError::InvalidCountryLength.into());
     }
 
@@ -63,5 +62,13 @@ pub fn register_participant(
     participant.country = params.country.to_string();
     participant.active = true;
 
+    let clock = Clock::get()?;
+    emit!(ParticipantRegistered {
+        participant: participant.key(),
+        owner: participant.owner,
+        country: participant.country.to_string(),
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,141 @@
+//! ReleaseCancelledJackpot instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::CancelledJackpotReleased,
+        state::{
+            deposit_base::DepositBase,
+            lottery_game::{LotteryGame, PaymentMode},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct ReleaseCancelledJackpot<'info> {
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game.is_cancelled @ LotteryGameError::LotteryGameNotCancelled,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // a cancelled lottery game never starts another round, its jackpot can't roll on
+    #[account(mut,has_one = lottery_game,
+        constraint = matches!(
+            lottery_round.status,
+            LotteryRoundStatus::Refundable | LotteryRoundStatus::Unwon
+        ) @ LotteryGameError::NothingToRelease,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_game.current_round.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.reserve_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub reserve_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // sol lottery games pay the jackpot to this wallet instead
+    #[account(mut, address = lottery_game.reserve_account @ LotteryGameError::InvalidRevenueAccount)]
+    pub reserve_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // anyone may route a cancelled lottery game's jackpot to the reserve
+    pub signer: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_cancelled_jackpot(ctx: Context<ReleaseCancelledJackpot>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    // ticket sales not refunded yet stay in the round for their ticket holders, only
+    // the jackpot carried in from earlier rounds is released
+    let refunds_owed = lottery_round
+        .amounts_raised
+        .checked_sub(lottery_round.amounts_refunded)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    let _amount = lottery_round
+        .available_funds
+        .checked_sub(refunds_owed)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    if _amount == 0 {
+        return Err(LotteryGameError::NothingToRelease.into());
+    }
+
+    // Deduct the released jackpot from the round's prize pool and total_available_funds
+    lottery_round.available_funds = refunds_owed;
+
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_reserve = lottery_game
+        .total_reserve
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (Some(sender_tokens), Some(reserve_tokens), Some(mint_token), Some(token_program)) = (
+                &ctx.accounts.sender_tokens,
+                &ctx.accounts.reserve_tokens,
+                &ctx.accounts.mint_token,
+                &ctx.accounts.token_program,
+            ) else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_tokens(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &reserve_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay lamports out of the treasury vault
+        PaymentMode::Sol => {
+            let Some(reserve_wallet) = &ctx.accounts.reserve_wallet else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_lamports(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &reserve_wallet.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                _amount,
+            )?
+        }
+    }
+
+    let clock = Clock::get()?;
+    emit!(CancelledJackpotReleased {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        reserve: lottery_game.reserve_account,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
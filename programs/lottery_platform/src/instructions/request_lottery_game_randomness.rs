@@ -0,0 +1,94 @@
+//! RequestLotteryGameRandomness instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::{DrawRequested, RoundRefundable},
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::{DrawMethod, LotteryGame},
+            lottery_operator::{LotteryOperator, OperatorStatus},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct RequestLotteryGameRandomness<'info> {
+    // any draw authority can request the oracle randomness, the lottery game admin
+    // holds no secret the draw depends on
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_draw_authority(draw_authority.key) @ LotteryGameError::MissingDrawRole,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = !lottery_game.is_cancelled @ LotteryGameError::LotteryGameCancelled,
+        constraint = lottery_game.draw_method == DrawMethod::Oracle @ LotteryGameError::InvalidDrawMethod,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // suspended operators can't draw their lottery games
+    #[account(
+        address = lottery_game.operator @ LotteryGameError::InvalidOperator,
+        constraint = lottery_operator.status == OperatorStatus::Active @ LotteryGameError::OperatorSuspended
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Open @ LotteryGameError::LotteryGameClosed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    pub draw_authority: Signer<'info>,
+}
+
+pub fn request_lottery_game_randomness(ctx: Context<RequestLotteryGameRandomness>) -> Result<()> {
+    let lottery_game = &ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < lottery_round.draw_at {
+        return Err(LotteryGameError::DrawTooEarly.into());
+    }
+
+    // too few tickets were sold, the round is refunded instead of drawn
+    if lottery_round.missed_min_tickets(lottery_game, clock.unix_timestamp) {
+        lottery_round.status = LotteryRoundStatus::Refundable;
+        emit!(RoundRefundable::new(lottery_round, clock.unix_timestamp));
+        return Ok(());
+    }
+
+    if lottery_round.tickets_sold == 0 {
+        return Err(LotteryGameError::InvalidParticipants.into());
+    }
+
+    // Close ticket sales and record the pending request the oracle has to fulfil
+    lottery_round.draw_slot = clock.slot;
+    lottery_round.randomness_request_id = lottery_round
+        .randomness_request_id
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.status = LotteryRoundStatus::DrawPending;
+
+    emit!(DrawRequested {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        draw_method: lottery_game.draw_method,
+        draw_slot: lottery_round.draw_slot,
+        randomness_request_id: lottery_round.randomness_request_id,
+        tickets_sold: lottery_round.tickets_sold,
+        amounts_raised: lottery_round.amounts_raised,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
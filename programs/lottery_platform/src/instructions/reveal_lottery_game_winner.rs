@@ -0,0 +1,143 @@
+//! RevealLotteryGameWinner instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::{RoundRefundable, WinnerDrawn},
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::{DrawMethod, LotteryGame},
+            lottery_operator::{LotteryOperator, OperatorStatus},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::{
+        hash::{hash, hashv},
+        sysvar::{clock::Clock, slot_hashes},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: RevealLotteryGameWinnerParams)]
+pub struct RevealLotteryGameWinner<'info> {
+    // only draw authorities can draw the winners
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_draw_authority(owner.key) @ LotteryGameError::MissingDrawRole,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = lottery_game.draw_method == DrawMethod::CommitReveal @ LotteryGameError::InvalidDrawMethod,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // suspended operators can't draw their lottery games
+    #[account(
+        address = lottery_game.operator @ LotteryGameError::InvalidOperator,
+        constraint = lottery_operator.status == OperatorStatus::Active @ LotteryGameError::OperatorSuspended
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::DrawPending @ LotteryGameError::DrawNotPending,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(address = slot_hashes::ID)]
+    /// CHECK: address is checked, data is parsed manually as the sysvar is too large to deserialize.
+    pub recent_slothashes: UncheckedAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevealLotteryGameWinnerParams {
+    pub seed: [u8; 32], // secret draw seed committed to at registration
+}
+
+// SlotHashes layout: u64 entry count followed by (u64 slot, [u8; 32] hash) entries, newest first
+const SLOT_HASHES_HEADER_LENGTH: usize = 8;
+const SLOT_HASH_ENTRY_LENGTH: usize = 40;
+
+pub fn reveal_lottery_game_winner(
+    ctx: Context<RevealLotteryGameWinner>,
+    params: &RevealLotteryGameWinnerParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    if hash(&params.seed).to_bytes() != lottery_round.draw_commitment {
+        return Err(LotteryGameError::InvalidDrawCommitment.into());
+    }
+
+    let clock = Clock::get()?;
+    let lottery_game_configs = &ctx.accounts.lottery_game_configs;
+    let reveal_deadline =
+        lottery_round.draw_window_end(&ctx.accounts.lottery_game, lottery_game_configs)?;
+
+    if clock.slot > reveal_deadline {
+        return Err(LotteryGameError::RevealWindowExpired.into());
+    }
+
+    // The entropy is the hash of the first slot produced after sales were closed, so neither
+    // the time of the reveal (or of a pause) nor the seed chosen at registration can steer it.
+    let slot_hash = match first_slot_hash_after(
+        &ctx.accounts.recent_slothashes.try_borrow_data()?,
+        lottery_round.draw_slot,
+    )? {
+        Some(slot_hash) => slot_hash,
+        None => {
+            // the entropy slot aged out of the sysvar, e.g during a long pause; drawing from
+            // a later slot would let the draw be re-rolled, the tickets are refunded instead
+            lottery_round.status = LotteryRoundStatus::Refundable;
+            emit!(RoundRefundable::new(lottery_round, clock.unix_timestamp));
+            return Ok(());
+        }
+    };
+
+    let randomness = hashv(&[&params.seed, &slot_hash, lottery_round.key().as_ref()]).to_bytes();
+
+    lottery_round.settle_draw(&mut ctx.accounts.lottery_game, randomness)?;
+
+    // pick numbers rounds only know their winners once every ticket is tallied
+    if lottery_round.status == LotteryRoundStatus::Closed {
+        emit!(WinnerDrawn::new(lottery_round));
+    }
+
+    Ok(())
+}
+
+// Hash of the first slot after `slot`, None once `slot` is no longer covered by the sysvar.
+fn first_slot_hash_after(data: &[u8], slot: u64) -> Result<Option<[u8; 32]>> {
+    let mut slot_hash: Option<[u8; 32]> = None;
+    let mut offset = SLOT_HASHES_HEADER_LENGTH;
+
+    while offset + SLOT_HASH_ENTRY_LENGTH <= data.len() {
+        let mut entry_slot = [0u8; 8];
+        entry_slot.copy_from_slice(&data[offset..offset + 8]);
+
+        // entries are newest first, so the last one seen above `slot` is its successor
+        if u64::from_le_bytes(entry_slot) <= slot {
+            // the successor hasn't been produced yet
+            return slot_hash
+                .map(Some)
+                .ok_or(LotteryGameError::DrawEntropyUnavailable.into());
+        }
+
+        let mut entry_hash = [0u8; 32];
+        entry_hash.copy_from_slice(&data[offset + 8..offset + SLOT_HASH_ENTRY_LENGTH]);
+        slot_hash = Some(entry_hash);
+        offset += SLOT_HASH_ENTRY_LENGTH;
+    }
+
+    // `slot` is no longer covered by the sysvar, its successor can't be identified
+    Ok(None)
+}
@@ -0,0 +1,55 @@
+//! RevokeRole instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::RoleRevoked,
+        state::configs::{AdminRole, LotteryGameConfigs},
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: RevokeRoleParams)]
+pub struct RevokeRole<'info> {
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    pub role: AdminRole,   // role being revoked
+    pub authority: Pubkey, // signer the role is revoked from
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>, params: &RevokeRoleParams) -> Result<()> {
+    let authorities = ctx.accounts.lottery_game_configs.authorities(params.role);
+
+    let Some(index) = authorities
+        .iter()
+        .position(|authority| *authority == params.authority)
+    else {
+        return Err(LotteryGameError::RoleNotGranted.into());
+    };
+
+    authorities.remove(index);
+
+    let clock = Clock::get()?;
+    emit!(RoleRevoked {
+        role: params.role,
+        authority: params.authority,
+        super_admin: *ctx.accounts.owner.key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
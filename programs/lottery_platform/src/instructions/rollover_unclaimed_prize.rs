@@ -0,0 +1,93 @@
+//! RolloverUnclaimedPrize instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::UnclaimedPrizeRolledOver,
+        state::{
+            lottery_game::{LotteryGame, RolloverRule},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct RolloverUnclaimedPrize<'info> {
+    #[account(
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game.rollover_rule == RolloverRule::UnwonAndUnclaimed @ LotteryGameError::RolloverNotAllowed,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = unclaimed_round.status == LotteryRoundStatus::Closed @ LotteryGameError::LotteryRoundNotSettled,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), unclaimed_round.round_number.to_le_bytes().as_ref()],
+        bump = unclaimed_round.bump
+    )]
+    pub unclaimed_round: Account<'info, LotteryRound>,
+    #[account(mut,has_one = lottery_game,
+        constraint = current_round.status == LotteryRoundStatus::Open @ LotteryGameError::LotteryGameClosed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_game.current_round.to_le_bytes().as_ref()],
+        bump = current_round.bump
+    )]
+    pub current_round: Account<'info, LotteryRound>,
+    // anyone may roll an expired prize into the running jackpot
+    pub signer: Signer<'info>,
+}
+
+pub fn rollover_unclaimed_prize(ctx: Context<RolloverUnclaimedPrize>) -> Result<()> {
+    let lottery_game = &ctx.accounts.lottery_game;
+    let unclaimed_round = &mut ctx.accounts.unclaimed_round;
+    let current_round = &mut ctx.accounts.current_round;
+    let rollover_amount = unclaimed_round.available_funds;
+
+    if rollover_amount == 0 {
+        return Err(LotteryGameError::NothingToRollOver.into());
+    }
+
+    let clock = Clock::get()?;
+    let claim_period_end = unclaimed_round
+        .drawn_at
+        .checked_add(lottery_game.claim_period)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    if clock.unix_timestamp <= claim_period_end {
+        return Err(LotteryGameError::ClaimPeriodNotEnded.into());
+    }
+
+    // winners can claim until the claim deadline, their prizes have to stay in the round
+    if lottery_game.claim_deadline > 0
+        && !unclaimed_round.claim_deadline_passed(lottery_game, clock.unix_timestamp)?
+    {
+        return Err(LotteryGameError::ClaimDeadlineNotPassed.into());
+    }
+
+    // Move the unclaimed prize into the running round's jackpot
+    unclaimed_round.available_funds = 0;
+    unclaimed_round.rolled_over_amount = unclaimed_round
+        .rolled_over_amount
+        .checked_add(rollover_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    current_round.rollover_amount = current_round
+        .rollover_amount
+        .checked_add(rollover_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    current_round.available_funds = current_round
+        .available_funds
+        .checked_add(rollover_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    emit!(UnclaimedPrizeRolledOver {
+        lottery_game: lottery_game.key(),
+        unclaimed_round_number: unclaimed_round.round_number,
+        current_round_number: current_round.round_number,
+        amount: rollover_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
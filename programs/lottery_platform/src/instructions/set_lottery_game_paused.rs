@@ -0,0 +1,67 @@
+//! SetLotteryGamePaused instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::LotteryGamePauseUpdated,
+        state::{configs::LotteryGameConfigs, lottery_game::LotteryGame},
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: SetLotteryGamePausedParams)]
+pub struct SetLotteryGamePaused<'info> {
+    // only guardian authorities can pause or unpause lottery games
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_guardian_authority(owner.key) @ LotteryGameError::MissingGuardianRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetLotteryGamePausedParams {
+    pub paused: bool,     // pause or unpause ticket sales and draws of the lottery game
+    pub reason_code: u16, // reason for the change, as coded by the guardians
+}
+
+pub fn set_lottery_game_paused(
+    ctx: Context<SetLotteryGamePaused>,
+    params: &SetLotteryGamePausedParams,
+) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+
+    if lottery_game.paused == params.paused {
+        return Err(LotteryGameError::PauseStateUnchanged.into());
+    }
+
+    // refunds and prize claims carry on while the lottery game is paused
+    let clock = Clock::get()?;
+    lottery_game.paused = params.paused;
+    if !params.paused {
+        lottery_game.unpaused_slot = clock.slot;
+    }
+
+    emit!(LotteryGamePauseUpdated {
+        lottery_game: lottery_game.key(),
+        guardian: *ctx.accounts.owner.key,
+        paused: params.paused,
+        reason_code: params.reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
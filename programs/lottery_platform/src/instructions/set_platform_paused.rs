@@ -0,0 +1,58 @@
+//! SetPlatformPaused instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError, events::PlatformPauseUpdated, state::configs::LotteryGameConfigs,
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: SetPlatformPausedParams)]
+pub struct SetPlatformPaused<'info> {
+    // only guardian authorities can pause or unpause the platform
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_guardian_authority(owner.key) @ LotteryGameError::MissingGuardianRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPlatformPausedParams {
+    pub paused: bool,     // pause or unpause ticket sales and draws of all lottery games
+    pub reason_code: u16, // reason for the change, as coded by the guardians
+}
+
+pub fn set_platform_paused(
+    ctx: Context<SetPlatformPaused>,
+    params: &SetPlatformPausedParams,
+) -> Result<()> {
+    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+
+    if lottery_game_configs.paused == params.paused {
+        return Err(LotteryGameError::PauseStateUnchanged.into());
+    }
+
+    // refunds and prize claims carry on while the platform is paused
+    let clock = Clock::get()?;
+    lottery_game_configs.paused = params.paused;
+    if !params.paused {
+        lottery_game_configs.unpaused_slot = clock.slot;
+    }
+
+    emit!(PlatformPauseUpdated {
+        guardian: *ctx.accounts.owner.key,
+        paused: params.paused,
+        reason_code: params.reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! StartNextRound instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::RoundStarted,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::{DrawMethod, LotteryGame, RolloverRule},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: StartNextRoundParams)]
+pub struct StartNextRound<'info> {
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.is_cancelled @ LotteryGameError::LotteryGameCancelled,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_game.current_round.to_le_bytes().as_ref()],
+        bump = previous_round.bump
+    )]
+    pub previous_round: Account<'info, LotteryRound>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LotteryRound::INIT_SPACE,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), (lottery_game.current_round + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_round: Account<'info, LotteryRound>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StartNextRoundParams {
+    draw_commitment: [u8; 32], // sha256 hash of the operator's secret draw seed (commit-reveal)
+    sales_open_at: i64,        // unix timestamp from which the round's tickets can be bought
+    sales_close_at: i64, // unix timestamp from which the round's tickets can no longer be bought
+    draw_at: i64,        // unix timestamp from which the round can be drawn
+}
+
+pub fn start_next_round(ctx: Context<StartNextRound>, params: &StartNextRoundParams) -> Result<()> {
+    msg!("Validate inputs");
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let previous_round = &mut ctx.accounts.previous_round;
+    let next_round = &mut ctx.accounts.next_round;
+
+    if lottery_game.draw_method == DrawMethod::CommitReveal && params.draw_commitment == [0u8; 32] {
+        return Err(LotteryGameError::InvalidDrawCommitment.into());
+    }
+
+    // A round without tickets has nothing to draw, it simply ends unwon
+    let clock = Clock::get()?;
+    if previous_round.status == LotteryRoundStatus::Open && previous_round.tickets_sold == 0 {
+        previous_round.status = LotteryRoundStatus::Unwon;
+    } else if previous_round.missed_min_tickets(lottery_game, clock.unix_timestamp) {
+        previous_round.status = LotteryRoundStatus::Refundable;
+    }
+
+    // Only the pool nobody can win any more rolls over here, unclaimed prizes
+    // roll over separately once their claim period has ended
+    let rollover_amount = match previous_round.status {
+        LotteryRoundStatus::Open
+        | LotteryRoundStatus::DrawPending
+        | LotteryRoundStatus::Tallying => {
+            return Err(LotteryGameError::LotteryRoundNotSettled.into());
+        }
+        _ if lottery_game.rollover_rule == RolloverRule::None => 0,
+        // prize shares of pick numbers tiers nobody matched can't be claimed
+        LotteryRoundStatus::Closed => previous_round
+            .unwon_amount
+            .min(previous_round.available_funds),
+        LotteryRoundStatus::Unwon => previous_round.available_funds,
+        // ticket sales are owed back to participants, only the carried jackpot moves on
+        LotteryRoundStatus::Refundable => previous_round
+            .rollover_amount
+            .min(previous_round.available_funds),
+    };
+
+    previous_round.available_funds = previous_round
+        .available_funds
+        .checked_sub(rollover_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    previous_round.rolled_over_amount = previous_round
+        .rolled_over_amount
+        .checked_add(rollover_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    let round_number = lottery_game
+        .current_round
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    // next_round
+    next_round.lottery_game = lottery_game.key();
+    next_round.round_number = round_number;
+    next_round.bump = ctx.bumps.next_round;
+    next_round.status = LotteryRoundStatus::Open;
+    next_round.schedule(params.sales_open_at, params.sales_close_at, params.draw_at)?;
+    next_round.first_ticket_number = lottery_game.tickets_sold;
+    next_round.rollover_amount = rollover_amount;
+    next_round.available_funds = rollover_amount;
+    next_round.draw_commitment = params.draw_commitment;
+    next_round.is_initialized = true;
+
+    // lottery_game
+    lottery_game.current_round = round_number;
+
+    emit!(RoundStarted {
+        lottery_game: lottery_game.key(),
+        round_number,
+        previous_round_status: previous_round.status,
+        rollover_amount,
+        sales_open_at: next_round.sales_open_at,
+        sales_close_at: next_round.sales_close_at,
+        draw_at: next_round.draw_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! SweepUnclaimedPrizes instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::UnclaimedPrizesSwept,
+        state::{
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{LotteryGame, PaymentMode},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedPrizes<'info> {
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Closed @ LotteryGameError::LotteryRoundNotSettled,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut,
+        address = lottery_game.unclaimed_prizes_beneficiary @ LotteryGameError::InvalidBeneficiaryAccount
+    )]
+    pub beneficiary_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // sol lottery games pay the unclaimed prizes to this wallet instead
+    #[account(mut,
+        address = lottery_game.unclaimed_prizes_beneficiary @ LotteryGameError::InvalidBeneficiaryAccount
+    )]
+    pub beneficiary_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sweep_unclaimed_prizes(ctx: Context<SweepUnclaimedPrizes>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    // prizes of the round that were never withdrawn
+    let _amount = lottery_round.available_funds;
+
+    let clock = Clock::get()?;
+    if !lottery_round.claim_deadline_passed(lottery_game, clock.unix_timestamp)? {
+        return Err(LotteryGameError::ClaimDeadlineNotPassed.into());
+    }
+
+    if _amount == 0 {
+        return Err(LotteryGameError::NothingToSweep.into());
+    }
+
+    // Deduct the swept prizes from the round's prize pool and total_available_funds
+    lottery_round.available_funds = 0;
+    lottery_round.swept_amount = lottery_round
+        .swept_amount
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_game.total_amounts_swept = lottery_game
+        .total_amounts_swept
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(beneficiary_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (
+                &ctx.accounts.sender_tokens,
+                &ctx.accounts.beneficiary_tokens,
+                &ctx.accounts.mint_token,
+                &ctx.accounts.token_program,
+            )
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_tokens(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &beneficiary_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay lamports out of the treasury vault
+        PaymentMode::Sol => {
+            let Some(beneficiary_wallet) = &ctx.accounts.beneficiary_wallet else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_lamports(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &beneficiary_wallet.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                _amount,
+            )?
+        }
+    }
+
+    emit!(UnclaimedPrizesSwept {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        beneficiary: lottery_game.unclaimed_prizes_beneficiary,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! TallyLotteryRoundTickets instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::WinnerDrawn,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_game::{GameMode, LotteryGame},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+            ticket::Ticket,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct TallyLotteryRoundTickets<'info> {
+    // draws are blocked while the platform is paused
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game_configs.paused @ LotteryGameError::PlatformPaused,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = !lottery_game.paused @ LotteryGameError::LotteryGamePaused,
+        constraint = lottery_game.game_mode == GameMode::PickNumbers @ LotteryGameError::InvalidGameMode,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Tallying @ LotteryGameError::TallyNotPending,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
+    )]
+    pub lottery_round: Account<'info, LotteryRound>,
+    // anyone may tally the round's tickets, they are passed as remaining accounts
+    // continuing from the last tallied ticket number, as many as fit in a transaction
+    pub signer: Signer<'info>,
+}
+
+pub fn tally_lottery_round_tickets<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TallyLotteryRoundTickets<'info>>,
+) -> Result<()> {
+    let lottery_game = &ctx.accounts.lottery_game;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+
+    if ctx.remaining_accounts.is_empty() {
+        return Err(LotteryGameError::InvalidTallyOrder.into());
+    }
+
+    for account_info in ctx.remaining_accounts.iter() {
+        let ticket = Account::<Ticket>::try_from(account_info)?;
+        lottery_round.tally_ticket(lottery_game, &ticket)?;
+    }
+
+    // pick numbers rounds only know their winners once every ticket is tallied
+    if lottery_round.status == LotteryRoundStatus::Closed {
+        emit!(WinnerDrawn::new(lottery_round));
+    }
+
+    Ok(())
+}
@@ -2,9 +2,8 @@
 
 use {
     crate::{error::LotteryGameError, state::lottery_game::LotteryGame},
-    anchor_lang::{prelude::*, system_program},
+    anchor_lang::prelude::*,
     anchor_spl::{
-        associated_token,
         associated_token::AssociatedToken,
         token::{transfer, Token, TokenAccount, Transfer},
     },
@@ -14,7 +13,9 @@ use {
 #[instruction(params: TransferTokenParams)]
 pub struct TransferToken<'info> {
     #[account(mut,
-        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
     )]
     pub lottery_game: Account<'info, LotteryGame>,
     #[account(mut)]
@@ -33,7 +34,7 @@ pub struct TransferToken<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct TransferTokenParams {
-    pub amount: u32,
+    pub amount: u64, // amount to transfer in base units
 }
 
 pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams) -> Result<()> {
@@ -42,20 +43,8 @@ pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams)
         return Err(LotteryGameError::InvalidAmount.into());
     }
 
-    let lottery_game = &ctx.accounts.lottery_game;
-    let decimals = lottery_game.decimals;
     let _amount = params.amount;
 
-    let base: u32 = 10;
-    let exponent = lottery_game.decimals as u32;
-
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
-        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
-
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
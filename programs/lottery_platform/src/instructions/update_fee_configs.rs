@@ -0,0 +1,70 @@
+//! UpdateFeeConfigs instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::FeeConfigsUpdated,
+        state::{configs::LotteryGameConfigs, lottery_game::BASIS_POINTS},
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: UpdateFeeConfigsParams)]
+pub struct UpdateFeeConfigs<'info> {
+    #[account(mut,
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateFeeConfigsParams {
+    operator_fee_percentage: u32, // default share of every ticket sale charged as the operator fee (basis points)
+    protocol_fee_percentage: u32, // share of every ticket sale charged as the protocol fee (basis points)
+    protocol_fee_account: Pubkey, // wallet receiving the protocol fees (its associated token account, token)
+}
+
+pub fn update_fee_configs(
+    ctx: Context<UpdateFeeConfigs>,
+    params: &UpdateFeeConfigsParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    let total_fee_percentage = params
+        .operator_fee_percentage
+        .checked_add(params.protocol_fee_percentage)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    if total_fee_percentage > BASIS_POINTS {
+        return Err(LotteryGameError::InvalidPercentage.into());
+    }
+
+    // protocol fees need somewhere to go
+    if params.protocol_fee_percentage > 0 && params.protocol_fee_account == Pubkey::default() {
+        return Err(LotteryGameError::InvalidFeeAccount.into());
+    }
+
+    // only lottery games registered from now on are charged the new fees
+    let lottery_game_configs = &mut ctx.accounts.lottery_game_configs;
+    lottery_game_configs.operator_fee_percentage = params.operator_fee_percentage;
+    lottery_game_configs.protocol_fee_percentage = params.protocol_fee_percentage;
+    lottery_game_configs.protocol_fee_account = params.protocol_fee_account;
+
+    let clock = Clock::get()?;
+    emit!(FeeConfigsUpdated {
+        operator_fee_percentage: params.operator_fee_percentage,
+        protocol_fee_percentage: params.protocol_fee_percentage,
+        protocol_fee_account: params.protocol_fee_account,
+        super_admin: *ctx.accounts.owner.key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! UpdateOperatorStatus instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::OperatorStatusUpdated,
+        state::{
+            configs::LotteryGameConfigs,
+            lottery_operator::{LotteryOperator, OperatorStatus},
+        },
+    },
+    anchor_lang::prelude::*,
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+#[instruction(params: UpdateOperatorStatusParams)]
+pub struct UpdateOperatorStatus<'info> {
+    // only the super admin can suspend or reinstate operators
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,
+        constraint = lottery_operator.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-operator", lottery_operator.authority.as_ref()],
+        bump = lottery_operator.bump
+    )]
+    pub lottery_operator: Account<'info, LotteryOperator>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateOperatorStatusParams {
+    pub status: OperatorStatus, // new status of the operator
+}
+
+pub fn update_operator_status(
+    ctx: Context<UpdateOperatorStatus>,
+    params: &UpdateOperatorStatusParams,
+) -> Result<()> {
+    // a suspended operator's lottery games keep selling tickets and paying out prizes,
+    // only new lottery games and draws are blocked
+    let lottery_operator = &mut ctx.accounts.lottery_operator;
+    lottery_operator.status = params.status;
+
+    let clock = Clock::get()?;
+    emit!(OperatorStatusUpdated {
+        lottery_operator: lottery_operator.key(),
+        status: params.status,
+        super_admin: *ctx.accounts.owner.key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,33 +3,66 @@
 use {
     crate::{
         error::LotteryGameError,
-        state::{deposit_base::DepositBase, lottery_game::LotteryGame, participant::Participant},
+        events::WinningsWithdrawn,
+        state::{
+            deposit_base::DepositBase,
+            game_entry::GameEntry,
+            lottery_game::{LotteryGame, PaymentMode},
+            lottery_round::{LotteryRound, LotteryRoundStatus},
+            ticket::Ticket,
+        },
     },
     anchor_lang::prelude::*,
     anchor_spl::{
-        associated_token::AssociatedToken,
-        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+        associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
+    solana_program::sysvar::clock::Clock,
 };
 
 #[derive(Accounts)]
-#[instruction(params: WithdrawLotteryGameWinningsParams)]
 pub struct WithdrawLotteryGameWinnings<'info> {
     #[account(mut,
         constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
-        constraint = lottery_game.is_closed @ LotteryGameError::LotteryGameClosed
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
     )]
     pub lottery_game: Account<'info, LotteryGame>,
-    #[account(mut,has_one = owner,
-        constraint = participant.active @ LotteryGameError::InvalidParticipantStatus
+    #[account(mut,has_one = lottery_game,
+        constraint = lottery_round.status == LotteryRoundStatus::Closed @ LotteryGameError::LotteryGameClosed,
+        seeds = [b"lottery-round", lottery_game.key().as_ref(), lottery_round.round_number.to_le_bytes().as_ref()],
+        bump = lottery_round.bump
     )]
-    pub participant: Account<'info, Participant>,
-    #[account(mut)]
-    pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub mint_token: Account<'info, Mint>,
+    pub lottery_round: Account<'info, LotteryRound>,
+    // the winner's entry in this lottery game, the claim doesn't rely on the Participant account
+    #[account(mut,has_one = owner, has_one = lottery_game,
+        seeds = [b"game-entry", lottery_game.key().as_ref(), game_entry.participant.as_ref()],
+        bump = game_entry.bump
+    )]
+    pub game_entry: Account<'info, GameEntry>,
+    // the winning ticket proves the claim, it must be owned by the caller
+    #[account(mut,has_one = owner, has_one = lottery_game,
+        constraint = ticket.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = ticket.round_number == lottery_round.round_number @ LotteryGameError::InvalidLotteryGameWinner,
+        seeds = [b"ticket", lottery_game.key().as_ref(), ticket.ticket_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    // prizes are only paid into the winner's associated token account for the lottery game's mint
+    #[account(mut,
+        constraint = recipient_tokens.mint == lottery_game.mint @ LotteryGameError::InvalidMint,
+        constraint = recipient_tokens.owner == owner.key() @ LotteryGameError::InvalidWinnerTokenAccount,
+        address = get_associated_token_address_with_program_id(
+            &owner.key(),
+            &lottery_game.mint,
+            recipient_tokens.to_account_info().owner
+        ) @ LotteryGameError::InvalidWinnerTokenAccount
+    )]
+    pub recipient_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
     #[account(mut,
         constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
     )]
@@ -37,32 +70,23 @@ pub struct WithdrawLotteryGameWinnings<'info> {
     #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
     /// CHECK: no need to check this.
     pub pda_auth: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
     pub treasury_vault: SystemAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub associate_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct WithdrawLotteryGameWinningsParams {
-    pub amount: u32,
-}
-
-pub fn withdraw_lottery_game_winnings(
-    ctx: Context<WithdrawLotteryGameWinnings>,
-    params: &WithdrawLotteryGameWinningsParams,
-) -> Result<()> {
-    msg!("Validate inputs");
-    if params.amount == 0 {
-        return Err(LotteryGameError::InvalidAmount.into());
-    }
-
+pub fn withdraw_lottery_game_winnings(ctx: Context<WithdrawLotteryGameWinnings>) -> Result<()> {
     let lottery_game = &mut ctx.accounts.lottery_game;
-    let participant = &mut ctx.accounts.participant;
+    let lottery_round = &mut ctx.accounts.lottery_round;
+    let game_entry = &mut ctx.accounts.game_entry;
+    let ticket = &mut ctx.accounts.ticket;
     let sender_tokens = &ctx.accounts.sender_tokens;
     let recipient_tokens = &ctx.accounts.recipient_tokens;
     let mint_token = &ctx.accounts.mint_token;
@@ -71,70 +95,103 @@ pub fn withdraw_lottery_game_winnings(
     let treasury_vault = &mut ctx.accounts.treasury_vault;
     let token_program = &ctx.accounts.token_program;
     let total_available_funds = lottery_game.total_available_funds;
-    let available_funds: u32 = participant.available_funds;
-    let decimals: u8 = lottery_game.decimals;
-    let _amount = params.amount;
-    let lottery_game_winner: Pubkey = lottery_game.lottery_game_winner;
+    let round_available_funds = lottery_round.available_funds;
 
-    if lottery_game_winner != *ctx.accounts.owner.key {
-        return Err(LotteryGameError::InvalidLotteryGameWinner.into());
+    // prizes not claimed by the claim deadline are swept to the beneficiary
+    let clock = Clock::get()?;
+    if lottery_round.claim_deadline_passed(lottery_game, clock.unix_timestamp)? {
+        return Err(LotteryGameError::ClaimDeadlinePassed.into());
     }
 
-    // participant's available funds should exceed zero
-    if available_funds == 0 {
+    // the ticket must have won one of the round's prize tiers, the winner is
+    // paid their tier's prize, computed on-chain at the draw
+    let _amount = lottery_round.claim_prize(lottery_game, ticket)?;
+
+    // prize should exceed zero
+    if _amount == 0 {
         return Err(LotteryGameError::InsufficientFunds.into());
     }
 
-    // treasury's available funds should exceed zero
-    if total_available_funds == 0 {
+    // round's available funds should exceed zero
+    if round_available_funds == 0 {
         return Err(LotteryGameError::InsufficientFunds.into());
     }
 
-    // treasury's available funds should match transfer amount
-    if total_available_funds == _amount {
-    } else {
-        return Err(LotteryGameError::MismatchedAmount.into());
+    // round's available funds should cover the prize
+    if round_available_funds < _amount {
+        return Err(LotteryGameError::InsufficientFunds.into());
     }
 
-    // Reset participant's available funds
-    participant.available_funds = 0;
-
-    // Reset participant's total units lottery ticket
-    participant.total_units_lottery_ticket = 0;
+    // Record the prize on the participant's entry in this lottery game only
+    game_entry.winnings = game_entry
+        .winnings
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    game_entry.prizes_claimed = game_entry
+        .prizes_claimed
+        .checked_add(1)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
     // Deduct actual_amount(sold unit_cost_of_lottery_ticket) from total_available_funds
     lottery_game.total_available_funds = total_available_funds
         .checked_sub(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
-
-    let base: u32 = 10;
-    let exponent = lottery_game.decimals as u32;
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    lottery_game.total_amounts_withdrawn = lottery_game
+        .total_amounts_withdrawn
+        .checked_add(_amount)
         .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    // Transfer funds from treasury vault to recipient
-    let cpi_accounts = TransferChecked {
-        from: sender_tokens.to_account_info(),
-        mint: mint_token.to_account_info(),
-        to: recipient_tokens.to_account_info(),
-        authority: treasury_vault.to_account_info(),
-    };
-
-    let seeds = &[
-        b"treasury-vault",
-        pda_auth.to_account_info().key.as_ref(),
-        &[deposit_account.admin_treasury_vault_bump.unwrap()],
-    ];
-
-    let signer = &[&seeds[..]];
+    // Deduct the same amount from the round's prize pool
+    lottery_round.available_funds = round_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+    lottery_round.amounts_withdrawn = lottery_round
+        .amounts_withdrawn
+        .checked_add(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
 
-    let cpi = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(recipient_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (sender_tokens, recipient_tokens, mint_token, token_program)
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            // Transfer funds from treasury vault to recipient, a transfer fee mint withholds its
+            // fee from the prize received while the vault is debited the full prize
+            deposit_account.transfer_tokens(
+                &pda_auth.to_account_info(),
+                &treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &recipient_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay the winner lamports out of the treasury vault
+        PaymentMode::Sol => deposit_account.transfer_lamports(
+            &pda_auth.to_account_info(),
+            &treasury_vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            _amount,
+        )?,
+    }
 
-    transfer_checked(cpi, _amount, decimals)?;
+    emit!(WinningsWithdrawn {
+        lottery_game: lottery_game.key(),
+        round_number: lottery_round.round_number,
+        ticket_number: ticket.ticket_number,
+        owner: *ctx.accounts.owner.key,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
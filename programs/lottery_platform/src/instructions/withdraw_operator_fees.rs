@@ -0,0 +1,128 @@
+//! WithdrawOperatorFees instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::OperatorFeesWithdrawn,
+        state::{
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{LotteryGame, PaymentMode},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawOperatorFees<'info> {
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.is_operator_authority(owner.key) @ LotteryGameError::MissingOperatorRole,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.operator_revenue_account @ LotteryGameError::InvalidFeeAccount)]
+    pub operator_fee_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // sol lottery games pay the operator fees to this wallet instead
+    #[account(mut, address = lottery_game.operator_revenue_account @ LotteryGameError::InvalidFeeAccount)]
+    pub operator_fee_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdraw_operator_fees(ctx: Context<WithdrawOperatorFees>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    // operator fees of the lottery game's drawn rounds
+    let _amount = lottery_game.operator_fees_available;
+
+    if _amount == 0 {
+        return Err(LotteryGameError::NoFeesToWithdraw.into());
+    }
+
+    // Deduct the withdrawn fees from total_available_funds
+    lottery_game.operator_fees_available = 0;
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(operator_fee_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (
+                &ctx.accounts.sender_tokens,
+                &ctx.accounts.operator_fee_tokens,
+                &ctx.accounts.mint_token,
+                &ctx.accounts.token_program,
+            )
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_tokens(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &operator_fee_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay lamports out of the treasury vault
+        PaymentMode::Sol => {
+            let Some(operator_fee_wallet) = &ctx.accounts.operator_fee_wallet else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_lamports(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &operator_fee_wallet.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                _amount,
+            )?
+        }
+    }
+
+    let clock = Clock::get()?;
+    emit!(OperatorFeesWithdrawn {
+        lottery_game: lottery_game.key(),
+        recipient: lottery_game.operator_revenue_account,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! WithdrawProtocolFees instruction handler
+
+use {
+    crate::{
+        error::LotteryGameError,
+        events::ProtocolFeesWithdrawn,
+        state::{
+            configs::LotteryGameConfigs,
+            deposit_base::DepositBase,
+            lottery_game::{LotteryGame, PaymentMode},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::get_associated_token_address_with_program_id,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    solana_program::sysvar::clock::Clock,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    // only the super admin can withdraw the protocol fees
+    #[account(
+        constraint = lottery_game_configs.is_initialized @ LotteryGameError::AccountNotInitialized,
+        constraint = lottery_game_configs.super_admin == owner.key() @ LotteryGameError::InvalidSuperAdmin,
+        seeds = [b"lottery-game-configs"],
+        bump
+    )]
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    #[account(mut,
+        constraint = lottery_game.is_initialized @ LotteryGameError::AccountNotInitialized,
+        seeds = [b"lottery-game", lottery_game.owner.as_ref(), lottery_game.game_id.to_le_bytes().as_ref()],
+        bump = lottery_game.bump
+    )]
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut, seeds = [b"vault-tokens", lottery_game.key().as_ref()], bump)]
+    pub sender_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    // protocol fees are only paid into the protocol fee wallet's associated token account
+    #[account(mut,
+        address = get_associated_token_address_with_program_id(
+            &lottery_game.protocol_fee_account,
+            &lottery_game.mint,
+            protocol_fee_tokens.to_account_info().owner
+        ) @ LotteryGameError::InvalidFeeAccount
+    )]
+    pub protocol_fee_tokens: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = lottery_game.mint @ LotteryGameError::InvalidMint)]
+    pub mint_token: Option<InterfaceAccount<'info, Mint>>,
+    // sol lottery games pay the protocol fees to this wallet instead
+    #[account(mut, address = lottery_game.protocol_fee_account @ LotteryGameError::InvalidFeeAccount)]
+    pub protocol_fee_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        constraint = deposit_account.is_initialized @ LotteryGameError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump,
+        address = lottery_game.treasury_vault @ LotteryGameError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+    let lottery_game = &mut ctx.accounts.lottery_game;
+    // protocol fees of the lottery game's drawn rounds
+    let _amount = lottery_game.protocol_fees_available;
+
+    if _amount == 0 {
+        return Err(LotteryGameError::NoFeesToWithdraw.into());
+    }
+
+    // Deduct the withdrawn fees from total_available_funds
+    lottery_game.protocol_fees_available = 0;
+    lottery_game.total_available_funds = lottery_game
+        .total_available_funds
+        .checked_sub(_amount)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+    match lottery_game.payment_mode {
+        PaymentMode::Token => {
+            let (
+                Some(sender_tokens),
+                Some(protocol_fee_tokens),
+                Some(mint_token),
+                Some(token_program),
+            ) = (
+                &ctx.accounts.sender_tokens,
+                &ctx.accounts.protocol_fee_tokens,
+                &ctx.accounts.mint_token,
+                &ctx.accounts.token_program,
+            )
+            else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_tokens(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &sender_tokens.to_account_info(),
+                &protocol_fee_tokens.to_account_info(),
+                mint_token,
+                &token_program.to_account_info(),
+                _amount,
+            )?;
+        }
+        // sol lottery games pay lamports out of the treasury vault
+        PaymentMode::Sol => {
+            let Some(protocol_fee_wallet) = &ctx.accounts.protocol_fee_wallet else {
+                return Err(LotteryGameError::InvalidPaymentMode.into());
+            };
+
+            ctx.accounts.deposit_account.transfer_lamports(
+                &ctx.accounts.pda_auth.to_account_info(),
+                &ctx.accounts.treasury_vault.to_account_info(),
+                &protocol_fee_wallet.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                _amount,
+            )?
+        }
+    }
+
+    let clock = Clock::get()?;
+    emit!(ProtocolFeesWithdrawn {
+        lottery_game: lottery_game.key(),
+        recipient: lottery_game.protocol_fee_account,
+        amount: _amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! lottery_platform program entrypoint
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -17,6 +18,10 @@ pub mod lottery_platform {
         instructions::init(ctx)
     }
 
+    pub fn migrate_configs(ctx: Context<MigrateConfigs>) -> Result<()> {
+        instructions::migrate_configs(ctx)
+    }
+
     pub fn register_lottery_game(
         ctx: Context<RegisterLotteryGame>,
         params: RegisterLotteryGameParams,
@@ -31,6 +36,103 @@ pub mod lottery_platform {
         instructions::get_lottery_game_winner(ctx, &params)
     }
 
+    pub fn reveal_lottery_game_winner(
+        ctx: Context<RevealLotteryGameWinner>,
+        params: RevealLotteryGameWinnerParams,
+    ) -> Result<()> {
+        instructions::reveal_lottery_game_winner(ctx, &params)
+    }
+
+    pub fn request_lottery_game_randomness(
+        ctx: Context<RequestLotteryGameRandomness>,
+    ) -> Result<()> {
+        instructions::request_lottery_game_randomness(ctx)
+    }
+
+    pub fn cancel_lottery_game(ctx: Context<CancelLotteryGame>) -> Result<()> {
+        instructions::cancel_lottery_game(ctx)
+    }
+
+    pub fn start_next_round(
+        ctx: Context<StartNextRound>,
+        params: StartNextRoundParams,
+    ) -> Result<()> {
+        instructions::start_next_round(ctx, &params)
+    }
+
+    pub fn migrate_lottery_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateLotteryGame<'info>>,
+        params: MigrateLotteryGameParams,
+    ) -> Result<()> {
+        instructions::migrate_lottery_game(ctx, &params)
+    }
+
+    pub fn sweep_unclaimed_prizes(ctx: Context<SweepUnclaimedPrizes>) -> Result<()> {
+        instructions::sweep_unclaimed_prizes(ctx)
+    }
+
+    pub fn update_fee_configs(
+        ctx: Context<UpdateFeeConfigs>,
+        params: UpdateFeeConfigsParams,
+    ) -> Result<()> {
+        instructions::update_fee_configs(ctx, &params)
+    }
+
+    pub fn withdraw_operator_fees(ctx: Context<WithdrawOperatorFees>) -> Result<()> {
+        instructions::withdraw_operator_fees(ctx)
+    }
+
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+        instructions::withdraw_protocol_fees(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::grant_role(ctx, &params)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::revoke_role(ctx, &params)
+    }
+
+    pub fn propose_super_admin(
+        ctx: Context<ProposeSuperAdmin>,
+        params: ProposeSuperAdminParams,
+    ) -> Result<()> {
+        instructions::propose_super_admin(ctx, &params)
+    }
+
+    pub fn accept_super_admin(ctx: Context<AcceptSuperAdmin>) -> Result<()> {
+        instructions::accept_super_admin(ctx)
+    }
+
+    pub fn register_operator(
+        ctx: Context<RegisterOperator>,
+        params: RegisterOperatorParams,
+    ) -> Result<()> {
+        instructions::register_operator(ctx, &params)
+    }
+
+    pub fn update_operator_status(
+        ctx: Context<UpdateOperatorStatus>,
+        params: UpdateOperatorStatusParams,
+    ) -> Result<()> {
+        instructions::update_operator_status(ctx, &params)
+    }
+
+    pub fn set_platform_paused(
+        ctx: Context<SetPlatformPaused>,
+        params: SetPlatformPausedParams,
+    ) -> Result<()> {
+        instructions::set_platform_paused(ctx, &params)
+    }
+
+    pub fn set_lottery_game_paused(
+        ctx: Context<SetLotteryGamePaused>,
+        params: SetLotteryGamePausedParams,
+    ) -> Result<()> {
+        instructions::set_lottery_game_paused(ctx, &params)
+    }
+
     // public instructions
     pub fn register_participant(
         ctx: Context<RegisterParticipant>,
@@ -46,6 +148,38 @@ pub mod lottery_platform {
         instructions::buy_lottery_ticket(ctx, &params)
     }
 
+    pub fn expire_lottery_game_draw(ctx: Context<ExpireLotteryGameDraw>) -> Result<()> {
+        instructions::expire_lottery_game_draw(ctx)
+    }
+
+    pub fn rollover_unclaimed_prize(ctx: Context<RolloverUnclaimedPrize>) -> Result<()> {
+        instructions::rollover_unclaimed_prize(ctx)
+    }
+
+    pub fn migrate_participant(ctx: Context<MigrateParticipant>) -> Result<()> {
+        instructions::migrate_participant(ctx)
+    }
+
+    pub fn tally_lottery_round_tickets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TallyLotteryRoundTickets<'info>>,
+    ) -> Result<()> {
+        instructions::tally_lottery_round_tickets(ctx)
+    }
+
+    pub fn distribute_lottery_round_proceeds(
+        ctx: Context<DistributeLotteryRoundProceeds>,
+    ) -> Result<()> {
+        instructions::distribute_lottery_round_proceeds(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund(ctx)
+    }
+
+    pub fn release_cancelled_jackpot(ctx: Context<ReleaseCancelledJackpot>) -> Result<()> {
+        instructions::release_cancelled_jackpot(ctx)
+    }
+
     pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
         instructions::create_token(ctx, &params)
     }
@@ -54,10 +188,15 @@ pub mod lottery_platform {
         instructions::transfer_token(ctx, &params)
     }
 
-    pub fn withdraw_lottery_game_winnings(
-        ctx: Context<WithdrawLotteryGameWinnings>,
-        params: WithdrawLotteryGameWinningsParams,
+    pub fn withdraw_lottery_game_winnings(ctx: Context<WithdrawLotteryGameWinnings>) -> Result<()> {
+        instructions::withdraw_lottery_game_winnings(ctx)
+    }
+
+    // oracle instructions
+    pub fn fulfil_lottery_game_randomness(
+        ctx: Context<FulfilLotteryGameRandomness>,
+        params: FulfilLotteryGameRandomnessParams,
     ) -> Result<()> {
-        instructions::withdraw_lottery_game_winnings(ctx, &params)
+        instructions::fulfil_lottery_game_randomness(ctx, &params)
     }
 }
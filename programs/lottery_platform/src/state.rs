@@ -2,6 +2,11 @@
 
 pub mod configs;
 pub mod deposit_base;
+pub mod game_entry;
 pub mod lottery_game;
+pub mod lottery_game_counter;
 pub mod lottery_operator;
+pub mod lottery_round;
 pub mod participant;
+pub mod prize_tier;
+pub mod ticket;
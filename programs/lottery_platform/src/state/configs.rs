@@ -1,10 +1,53 @@
-use crate::state::lottery_operator::LotteryOperator;
 use anchor_lang::prelude::*;
 
+// most authorities a role can be granted to
+pub const MAX_AUTHORITIES: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AdminRole {
+    Operator, // registers and runs lottery games
+    Draw,     // draws the winners of lottery games
+    Guardian, // pauses and unpauses the platform and lottery games
+}
+
 #[account]
 #[derive(Default, InitSpace)]
 pub struct LotteryGameConfigs {
-    #[max_len(5)]
-    pub operators: Vec<LotteryOperator>,
     pub is_initialized: bool,
+    pub super_admin: Pubkey, // grants and revokes roles, sets the fee configuration
+    pub pending_super_admin: Pubkey, // proposed super admin, has to accept before taking over
+    #[max_len(MAX_AUTHORITIES)]
+    pub operator_authorities: Vec<Pubkey>, // signers allowed to register and run lottery games
+    #[max_len(MAX_AUTHORITIES)]
+    pub draw_authorities: Vec<Pubkey>, // signers allowed to draw the winners of lottery games
+    #[max_len(MAX_AUTHORITIES)]
+    pub guardian_authorities: Vec<Pubkey>, // signers allowed to pause and unpause the platform and lottery games
+    pub paused: bool, // paused by a guardian, ticket sales and draws of all lottery games are blocked
+    pub unpaused_slot: u64, // slot at which the platform was last unpaused, pending draws get their deadline counted from it
+    pub operator_fee_percentage: u32, // default share of every ticket sale charged as the operator fee (basis points)
+    pub protocol_fee_percentage: u32, // share of every ticket sale charged as the protocol fee (basis points)
+    pub protocol_fee_account: Pubkey, // wallet receiving the protocol fees (its associated token account, token)
+}
+
+impl LotteryGameConfigs {
+    // Authorities currently holding the role.
+    pub fn authorities(&mut self, role: AdminRole) -> &mut Vec<Pubkey> {
+        match role {
+            AdminRole::Operator => &mut self.operator_authorities,
+            AdminRole::Draw => &mut self.draw_authorities,
+            AdminRole::Guardian => &mut self.guardian_authorities,
+        }
+    }
+
+    pub fn is_operator_authority(&self, authority: &Pubkey) -> bool {
+        self.operator_authorities.contains(authority)
+    }
+
+    pub fn is_draw_authority(&self, authority: &Pubkey) -> bool {
+        self.draw_authorities.contains(authority)
+    }
+
+    pub fn is_guardian_authority(&self, authority: &Pubkey) -> bool {
+        self.guardian_authorities.contains(authority)
+    }
 }
@@ -1,4 +1,11 @@
-use anchor_lang::prelude::*;
+use {
+    crate::error::LotteryGameError,
+    anchor_lang::{
+        prelude::*,
+        system_program::{transfer, Transfer},
+    },
+    anchor_spl::token_interface::{transfer_checked, Mint, TransferChecked},
+};
 
 #[account]
 #[derive(Default, Debug, InitSpace)]
@@ -8,3 +15,83 @@ pub struct DepositBase {
     pub admin_treasury_vault_bump: Option<u8>,
     pub is_initialized: bool,
 }
+
+impl DepositBase {
+    // pays lamports out of the treasury vault, which has to stay rent-exempt
+    pub fn transfer_lamports<'info>(
+        &self,
+        pda_auth: &AccountInfo<'info>,
+        treasury_vault: &AccountInfo<'info>,
+        recipient: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        lamports: u64,
+    ) -> Result<()> {
+        let remaining_lamports = treasury_vault
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(LotteryGameError::InsufficientFunds)?;
+
+        if remaining_lamports < Rent::get()?.minimum_balance(0) {
+            return Err(LotteryGameError::InsufficientFunds.into());
+        }
+
+        let seeds = &[
+            b"treasury-vault",
+            pda_auth.key.as_ref(),
+            &[self.treasury_vault_bump()?],
+        ];
+        let signer = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Transfer {
+                    from: treasury_vault.clone(),
+                    to: recipient.clone(),
+                },
+                signer,
+            ),
+            lamports,
+        )
+    }
+
+    // pays tokens out of the lottery game's token vault, whose authority is the treasury vault
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_tokens<'info>(
+        &self,
+        pda_auth: &AccountInfo<'info>,
+        treasury_vault: &AccountInfo<'info>,
+        vault_tokens: &AccountInfo<'info>,
+        recipient_tokens: &AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[
+            b"treasury-vault",
+            pda_auth.key.as_ref(),
+            &[self.treasury_vault_bump()?],
+        ];
+        let signer = &[&seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: vault_tokens.clone(),
+                    mint: mint.to_account_info(),
+                    to: recipient_tokens.clone(),
+                    authority: treasury_vault.clone(),
+                },
+                signer,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+
+    fn treasury_vault_bump(&self) -> Result<u8> {
+        self.admin_treasury_vault_bump
+            .ok_or(LotteryGameError::AccountNotInitialized.into())
+    }
+}
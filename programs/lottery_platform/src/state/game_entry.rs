@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct GameEntry {
+    pub owner: Pubkey,        // publickey of the participant
+    pub participant: Pubkey,  // participant account the entry belongs to
+    pub lottery_game: Pubkey, // lottery game the entry is for
+    pub bump: u8,             // bump of the game entry PDA
+    pub tickets_bought: u64,  // number of tickets bought in the lottery game
+    pub amount_spent: u64,    // amount paid for those tickets (base units)
+    pub amount_refunded: u64, // amount refunded for tickets of refundable rounds
+    pub winnings: u64,        // prizes withdrawn from the lottery game
+    pub prizes_claimed: u64,  // number of winning tickets whose prize has been withdrawn
+    pub is_initialized: bool,
+}
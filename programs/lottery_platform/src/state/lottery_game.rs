@@ -1,27 +1,94 @@
-use crate::state::lottery_operator::LotteryOperator;
+use crate::state::prize_tier::{PrizeTier, MAX_PRIZE_TIERS};
 use anchor_lang::prelude::*;
 
+// percentages are expressed in basis points, i.e 10_000 is 100%
+pub const BASIS_POINTS: u32 = 10_000;
+// most numbers a ticket can pick in a pick numbers lottery game
+pub const MAX_PICK_COUNT: usize = 10;
+// sol amounts are scaled to lamports, i.e 1 sol is 10^9 lamports
+pub const SOL_DECIMALS: u8 = 9;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum DrawMethod {
+    CommitReveal, // operator reveals a seed committed to at registration
+    Oracle,       // randomness is fulfilled by the configured oracle authority
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum GameMode {
+    Raffle,      // winning tickets are drawn from the tickets sold
+    PickNumbers, // tickets pick numbers, prizes are won by matching the drawn numbers
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PaymentMode {
+    Token, // tickets are paid in the game's token mint, held in the vault token account
+    Sol,   // tickets are paid in lamports, held in the treasury vault
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RolloverRule {
+    None,              // a round's leftover pool stays with that round
+    Unwon,             // pool of a round without a winner rolls into the next jackpot
+    UnwonAndUnclaimed, // unclaimed prizes roll into the next jackpot as well
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LotteryGame {
-    pub owner: Pubkey, // publickey of the lottery game admin
-    pub operator: LotteryOperator,
+    pub owner: Pubkey,    // publickey of the lottery game admin
+    pub game_id: u64,     // id of the lottery game, scoped to the lottery game admin
+    pub bump: u8,         // bump of the lottery game PDA
+    pub operator: Pubkey, // lottery operator account running the lottery game
     #[max_len(3)]
     pub country: String, // home country where lottery game is auctioned
     #[max_len(20)]
     pub lottery_game_name: String, // lottery game name
-    pub total_amounts_raised: u32, // total amounts raised from sale of tickets
-    pub total_amounts_withdrawn: u32, // total amounts withdrawn by participants i.e winners
-    pub total_available_funds: u32, // total available funds equal to the lottery game tickets bought by participants
-    pub is_initialized: bool,       // is lottery game initiated
-    #[max_len(10)]
-    pub participants: Vec<Pubkey>, // list of the participants
-    pub lottery_game_winner: Pubkey, // lottery game winner
-    pub lottery_game_winner_index: u32, // lottery game winner
-    pub lottery_winning_percentage: u32, // lottery winning percentage
-    pub unit_cost_of_lottery_ticket: u32, // unit cost of lottery ticket
-    pub decimals: u8,               // decimals for the token mint
+    pub total_amounts_raised: u64, // lifetime amounts raised from sale of tickets
+    pub total_amounts_withdrawn: u64, // lifetime amounts withdrawn by participants i.e winners
+    pub total_available_funds: u64, // funds currently held across all rounds of the lottery game
+    pub is_initialized: bool, // is lottery game initiated
+    pub is_cancelled: bool, // cancelled by the admin, tickets of unsettled rounds are refunded
+    pub cancelled_at: i64, // unix timestamp at which the lottery game was cancelled
+    pub paused: bool,     // paused by a guardian, ticket sales and draws are blocked
+    pub unpaused_slot: u64, // slot at which the lottery game was last unpaused, pending draws get their deadline counted from it
+    pub total_amounts_refunded: u64, // lifetime amounts refunded to participants
+    pub tickets_sold: u64,  // lifetime number of tickets sold, also the next ticket number
+    pub current_round: u64, // round number of the latest lottery round
+    pub rollover_rule: RolloverRule, // what happens to a round's leftover pool
+    pub claim_period: i64, // seconds a round winner has to claim before the prize may roll over, at least the claim deadline
+    pub claim_deadline: i64, // seconds after a round's draw its prizes can be claimed, 0 means no deadline
+    pub unclaimed_prizes_beneficiary: Pubkey, // token account (or wallet, sol) receiving prizes unclaimed by the deadline
+    pub total_amounts_swept: u64,             // lifetime unclaimed prizes swept to the beneficiary
+    pub lottery_winning_percentage: u32, // share of a round's ticket sales paid to the winner (basis points)
+    pub reserve_percentage: u32, // share of the non-prize remainder kept as reserve (basis points)
+    #[max_len(MAX_PRIZE_TIERS)]
+    pub prize_tiers: Vec<PrizeTier>, // prize table, tiers are drawn in order starting with the jackpot
+    pub operator_revenue_account: Pubkey, // token account receiving the operator's revenue
+    pub reserve_account: Pubkey,          // token account receiving the reserve
+    pub total_operator_revenue: u64,      // lifetime operator revenue paid out
+    pub total_reserve: u64,               // lifetime reserve paid out
+    pub operator_fee_percentage: u32, // share of every ticket sale charged as the operator fee (basis points)
+    pub protocol_fee_percentage: u32, // share of every ticket sale charged as the protocol fee (basis points)
+    pub protocol_fee_account: Pubkey, // wallet receiving the protocol fees (its associated token account, token)
+    pub total_prize_pool_amounts: u64, // lifetime ticket sales paid into the prize pools, net of fees
+    pub total_operator_fees: u64,      // lifetime operator fees charged on ticket sales
+    pub total_protocol_fees: u64,      // lifetime protocol fees charged on ticket sales
+    pub operator_fees_available: u64,  // operator fees of drawn rounds not yet withdrawn
+    pub protocol_fees_available: u64,  // protocol fees of drawn rounds not yet withdrawn
+    pub unit_cost_of_lottery_ticket: u64, // unit cost of lottery ticket (base units)
+    pub payment_mode: PaymentMode,     // currency the lottery game's tickets are paid in
+    pub treasury_vault: Pubkey,        // treasury vault PDA, signs payouts and holds lamports (sol)
+    pub mint: Pubkey,                  // token mint accepted by the lottery game (token)
+    pub decimals: u8,                  // decimals for the token mint
     #[max_len(20)]
     pub value_date: String, // value date of lottery draw
-    pub is_closed: bool,            // is lottery game closed
+    pub min_tickets: u64, // tickets a round has to sell to be drawn, 0 means no minimum
+    pub max_tickets_per_purchase: u64, // tickets a single purchase can buy, 0 means no limit
+    pub max_tickets_per_game: u64, // tickets the lottery game can sell over all its rounds, 0 means no limit
+    pub draw_method: DrawMethod,   // source of the draw randomness
+    pub game_mode: GameMode,       // how the round winners are determined
+    pub pick_count: u8,            // numbers picked per ticket, e.g 6 in a 6/49 game (pick numbers)
+    pub number_range: u8, // numbers are picked from 1 up to this number, e.g 49 (pick numbers)
+    pub oracle_authority: Pubkey, // only signer allowed to fulfil oracle randomness
 }
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct LotteryGameCounter {
+    pub owner: Pubkey,     // publickey of the lottery game admin
+    pub next_game_id: u64, // game id assigned to the admin's next lottery game
+    pub is_initialized: bool,
+}
@@ -1,7 +1,24 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+// most countries an operator can be licensed in
+pub const MAX_LICENSED_COUNTRIES: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OperatorStatus {
+    Active,    // can register lottery games and draw them
+    Suspended, // can't register lottery games or draw them, sales and claims carry on
+}
+
+#[account]
+#[derive(InitSpace)]
 pub struct LotteryOperator {
+    pub authority: Pubkey, // signer registering and running the operator's lottery games
+    pub bump: u8,          // bump of the lottery operator PDA
     #[max_len(30)]
-    pub operator: String, // company operating the lottery
+    pub name: String, // company operating the lottery
+    #[max_len(MAX_LICENSED_COUNTRIES, 3)]
+    pub licensed_countries: Vec<String>, // countries the operator can run lottery games in
+    pub status: OperatorStatus, // whether the operator can register and draw lottery games
+    pub game_count: u64,   // number of lottery games registered by the operator
+    pub is_initialized: bool,
 }
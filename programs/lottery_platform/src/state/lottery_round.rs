@@ -0,0 +1,713 @@
+use crate::{
+    error::LotteryGameError,
+    state::{
+        configs::LotteryGameConfigs,
+        lottery_game::{GameMode, LotteryGame, BASIS_POINTS, MAX_PICK_COUNT},
+        prize_tier::{MAX_PRIZE_TIERS, MAX_WINNERS},
+        ticket::Ticket,
+    },
+};
+use anchor_lang::prelude::*;
+use solana_program::hash::hashv;
+
+// number of slots the draw randomness has to arrive in once sales are closed,
+// kept below the depth of the SlotHashes sysvar (512) so the entropy slot stays readable
+pub const REVEAL_WINDOW_SLOTS: u64 = 300;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LotteryRoundStatus {
+    Open,        // tickets can be bought
+    DrawPending, // sales closed, waiting for the draw seed reveal or the oracle randomness
+    Closed,      // winner drawn
+    Refundable,  // draw never happened or game cancelled, participants are owed refunds
+    Unwon,       // round ended without a draw, e.g no tickets were sold
+    Tallying,    // winning numbers drawn, tickets are being matched against them
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct WinningTicket {
+    pub ticket_number: u64, // lottery game ticket number drawn as a winner
+    pub tier: u8,           // index of the prize tier the ticket won
+    pub prize_amount: u64,  // prize payable to the ticket's owner
+    pub is_claimed: bool,   // prize has been withdrawn
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct TierResult {
+    pub winners: u64,      // tickets that matched the tier's numbers
+    pub prize_amount: u64, // prize payable per winning ticket of the tier
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LotteryRound {
+    pub lottery_game: Pubkey,       // lottery game the round belongs to
+    pub round_number: u64,          // sequential round number within the lottery game
+    pub bump: u8,                   // bump of the lottery round PDA
+    pub status: LotteryRoundStatus, // current stage of the lottery round
+    pub first_ticket_number: u64,   // lottery game ticket number of the round's first ticket
+    pub tickets_sold: u64,          // number of tickets sold in the round
+    pub sales_open_at: i64,         // unix timestamp from which tickets can be bought
+    pub sales_close_at: i64,        // unix timestamp from which tickets can no longer be bought
+    pub draw_at: i64,               // unix timestamp from which the round can be drawn
+    pub amounts_raised: u64,        // amounts raised from sale of the round's tickets
+    pub operator_fees: u64, // operator fees charged on the round's ticket sales, released once drawn
+    pub protocol_fees: u64, // protocol fees charged on the round's ticket sales, released once drawn
+    pub rollover_amount: u64, // jackpot rolled over from the previous round
+    pub amounts_withdrawn: u64, // amounts withdrawn by the round's winners
+    pub amounts_refunded: u64, // amounts refunded to ticket holders of a refundable round
+    pub rolled_over_amount: u64, // unwon or unclaimed pool rolled into the next round
+    pub swept_amount: u64,  // unclaimed prizes swept to the beneficiary after the claim deadline
+    pub available_funds: u64, // prize pool still held for the round
+    pub draw_commitment: [u8; 32], // sha256 hash of the operator's secret draw seed
+    pub randomness_request_id: u64, // id of the latest oracle randomness request
+    pub draw_slot: u64,     // slot at which ticket sales were closed for the draw
+    pub randomness: [u8; 32], // randomness used to derive the round winners
+    #[max_len(MAX_WINNERS)]
+    pub winning_tickets: Vec<WinningTicket>, // tickets drawn as winners, in prize tier order (raffle)
+    #[max_len(MAX_PICK_COUNT)]
+    pub winning_numbers: Vec<u8>, // winning combination, sorted ascending (pick numbers)
+    pub tallied_tickets: u64, // tickets matched against the winning numbers so far (pick numbers)
+    #[max_len(MAX_PRIZE_TIERS)]
+    pub tier_results: Vec<TierResult>, // winners and prize of each prize tier (pick numbers)
+    pub drawn_at: i64,        // unix timestamp at which the winners were drawn
+    pub prize_amount: u64,    // prize pool shared among the round's prize tiers
+    pub unwon_amount: u64,    // prize shares of the tiers nobody matched (pick numbers)
+    pub operator_revenue_amount: u64, // share of the round's ticket sales owed to the operator
+    pub reserve_amount: u64,  // share of the round's ticket sales owed to the reserve
+    pub is_proceeds_distributed: bool, // operator revenue and reserve have been paid out
+    pub is_initialized: bool,
+}
+
+impl LotteryRound {
+    // Set the round's ticket sales window and the earliest time it can be drawn.
+    pub fn schedule(
+        &mut self,
+        sales_open_at: i64,
+        sales_close_at: i64,
+        draw_at: i64,
+    ) -> Result<()> {
+        if sales_open_at < sales_close_at && sales_close_at <= draw_at {
+        } else {
+            return Err(LotteryGameError::InvalidSchedule.into());
+        }
+
+        self.sales_open_at = sales_open_at;
+        self.sales_close_at = sales_close_at;
+        self.draw_at = draw_at;
+
+        Ok(())
+    }
+
+    // An open round whose sales closed below the lottery game's minimum number of
+    // tickets can't be drawn, its tickets are refunded instead.
+    pub fn missed_min_tickets(&self, lottery_game: &LotteryGame, now: i64) -> bool {
+        self.status == LotteryRoundStatus::Open
+            && now >= self.sales_close_at
+            && self.tickets_sold < lottery_game.min_tickets
+    }
+
+    // Prizes of a drawn round can no longer be claimed once the lottery game's claim
+    // deadline has passed, they are swept to the unclaimed prizes beneficiary instead.
+    pub fn claim_deadline_passed(&self, lottery_game: &LotteryGame, now: i64) -> Result<bool> {
+        if lottery_game.claim_deadline == 0 {
+            return Ok(false);
+        }
+
+        let claim_deadline = self
+            .drawn_at
+            .checked_add(lottery_game.claim_deadline)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        Ok(now > claim_deadline)
+    }
+
+    // Last slot the draw randomness can arrive in, the draw can be expired after it. A pause
+    // must not void a pending draw, so the window is counted from the slot the lottery game
+    // or the platform was last unpaused at, if later. Only the deadline moves, the draw
+    // entropy stays tied to `draw_slot`.
+    pub fn draw_window_end(
+        &self,
+        lottery_game: &LotteryGame,
+        lottery_game_configs: &LotteryGameConfigs,
+    ) -> Result<u64> {
+        self.draw_slot
+            .max(lottery_game.unpaused_slot)
+            .max(lottery_game_configs.unpaused_slot)
+            .checked_add(REVEAL_WINDOW_SLOTS)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation.into())
+    }
+
+    // Draw the round's winning tickets (raffle) or winning numbers (pick numbers) from the
+    // draw randomness and split the round's pool into fees, prize, operator revenue and reserve.
+    pub fn settle_draw(
+        &mut self,
+        lottery_game: &mut LotteryGame,
+        randomness: [u8; 32],
+    ) -> Result<()> {
+        if self.tickets_sold == 0 {
+            return Err(LotteryGameError::InvalidParticipants.into());
+        }
+
+        let clock = Clock::get()?;
+        self.randomness = randomness;
+        self.drawn_at = clock.unix_timestamp;
+
+        // fees come off the top of the ticket sales, they are withdrawable from now on
+        let fees = self
+            .operator_fees
+            .checked_add(self.protocol_fees)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        let net_sales = self
+            .amounts_raised
+            .checked_sub(fees)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        lottery_game.operator_fees_available = lottery_game
+            .operator_fees_available
+            .checked_add(self.operator_fees)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        lottery_game.protocol_fees_available = lottery_game
+            .protocol_fees_available
+            .checked_add(self.protocol_fees)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        // winners share the configured part of ticket sales plus any jackpot rolled in
+        let prize_share = apply_basis_points(net_sales, lottery_game.lottery_winning_percentage)?;
+        let remainder = net_sales
+            .checked_sub(prize_share)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        self.reserve_amount = apply_basis_points(remainder, lottery_game.reserve_percentage)?;
+        self.operator_revenue_amount = remainder
+            .checked_sub(self.reserve_amount)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        self.prize_amount = prize_share
+            .checked_add(self.rollover_amount)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        // only the prize stays in the round's pool, the rest is distributed separately.
+        // Prize shares not won, e.g when fewer tickets were sold than there are winners,
+        // stay in the pool and are treated like unclaimed prizes.
+        self.available_funds = self.prize_amount;
+
+        match lottery_game.game_mode {
+            GameMode::Raffle => {
+                self.draw_winning_tickets(lottery_game, &randomness)?;
+                self.status = LotteryRoundStatus::Closed;
+            }
+            GameMode::PickNumbers => {
+                // winners are only known once every ticket is matched, see `tally_ticket`
+                self.winning_numbers = draw_numbers(
+                    &randomness,
+                    lottery_game.pick_count,
+                    lottery_game.number_range,
+                );
+                self.tier_results = lottery_game
+                    .prize_tiers
+                    .iter()
+                    .map(|_| TierResult {
+                        winners: 0,
+                        prize_amount: 0,
+                    })
+                    .collect();
+                self.tallied_tickets = 0;
+                self.status = LotteryRoundStatus::Tallying;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Entries are drawn without replacement, so an entry wins at most one prize. A ticket
+    // holding several entries is drawn once for each, weighting its odds by its quantity.
+    fn draw_winning_tickets(
+        &mut self,
+        lottery_game: &LotteryGame,
+        randomness: &[u8; 32],
+    ) -> Result<()> {
+        let total_winners: u64 = lottery_game
+            .prize_tiers
+            .iter()
+            .map(|prize_tier| prize_tier.winners as u64)
+            .sum();
+        let offsets = draw_without_replacement(randomness, total_winners, self.tickets_sold);
+        let mut offsets = offsets.iter();
+        self.winning_tickets.clear();
+
+        for (tier, prize_tier) in lottery_game.prize_tiers.iter().enumerate() {
+            let first_winner = self.winning_tickets.len();
+
+            for offset in offsets.by_ref().take(prize_tier.winners as usize) {
+                self.winning_tickets.push(WinningTicket {
+                    ticket_number: self
+                        .first_ticket_number
+                        .checked_add(*offset)
+                        .ok_or(LotteryGameError::InvalidArithmeticOperation)?,
+                    tier: tier as u8,
+                    prize_amount: 0,
+                    is_claimed: false,
+                });
+            }
+
+            // the tier's share is split evenly among the tickets actually drawn for it
+            let tier_winners = (self.winning_tickets.len() - first_winner) as u64;
+            if tier_winners == 0 {
+                break;
+            }
+            let prize_amount =
+                apply_basis_points(self.prize_amount, prize_tier.prize_percentage)? / tier_winners;
+            for winning_ticket in self.winning_tickets[first_winner..].iter_mut() {
+                winning_ticket.prize_amount = prize_amount;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Count a ticket's entries towards the prize tier its numbers match. Tickets are counted
+    // in ticket number order, so each one is counted exactly once; after the last one the
+    // tiers' prizes are fixed and the round is closed.
+    pub fn tally_ticket(&mut self, lottery_game: &LotteryGame, ticket: &Ticket) -> Result<()> {
+        let ticket_number = self
+            .first_ticket_number
+            .checked_add(self.tallied_tickets)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        if ticket.lottery_game != self.lottery_game
+            || ticket.round_number != self.round_number
+            || ticket.ticket_number != ticket_number
+        {
+            return Err(LotteryGameError::InvalidTallyOrder.into());
+        }
+
+        if let Some(tier) = self.matching_tier(lottery_game, &ticket.numbers) {
+            let tier_result = &mut self.tier_results[tier];
+            tier_result.winners = tier_result
+                .winners
+                .checked_add(ticket.quantity)
+                .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+        }
+
+        self.tallied_tickets = self
+            .tallied_tickets
+            .checked_add(ticket.quantity)
+            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+
+        if self.tallied_tickets == self.tickets_sold {
+            // tickets with the same matches share the tier's prize
+            for (tier_result, prize_tier) in self
+                .tier_results
+                .iter_mut()
+                .zip(lottery_game.prize_tiers.iter())
+            {
+                let tier_prize =
+                    apply_basis_points(self.prize_amount, prize_tier.prize_percentage)?;
+                match tier_prize.checked_div(tier_result.winners) {
+                    Some(prize_amount) => tier_result.prize_amount = prize_amount,
+                    // a tier nobody matched keeps a zero prize, its share is unwon
+                    None => {
+                        self.unwon_amount = self
+                            .unwon_amount
+                            .checked_add(tier_prize)
+                            .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+                    }
+                }
+            }
+            self.status = LotteryRoundStatus::Closed;
+        }
+
+        Ok(())
+    }
+
+    // Mark the prizes of the ticket's winning entries as claimed and return the amount
+    // payable to its owner.
+    pub fn claim_prize(&mut self, lottery_game: &LotteryGame, ticket: &mut Ticket) -> Result<u64> {
+        let prize_amount = match lottery_game.game_mode {
+            GameMode::Raffle => {
+                let last_ticket_number = ticket
+                    .ticket_number
+                    .checked_add(ticket.quantity)
+                    .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+                let mut winning_entries = self
+                    .winning_tickets
+                    .iter_mut()
+                    .filter(|winning_ticket| {
+                        (ticket.ticket_number..last_ticket_number)
+                            .contains(&winning_ticket.ticket_number)
+                    })
+                    .peekable();
+
+                if winning_entries.peek().is_none() {
+                    return Err(LotteryGameError::InvalidLotteryGameWinner.into());
+                }
+
+                if ticket.is_prize_claimed {
+                    return Err(LotteryGameError::PrizeAlreadyClaimed.into());
+                }
+
+                let mut prize_amount: u64 = 0;
+                for winning_ticket in winning_entries {
+                    winning_ticket.is_claimed = true;
+                    prize_amount = prize_amount
+                        .checked_add(winning_ticket.prize_amount)
+                        .ok_or(LotteryGameError::InvalidArithmeticOperation)?;
+                }
+                prize_amount
+            }
+            GameMode::PickNumbers => {
+                let tier = self
+                    .matching_tier(lottery_game, &ticket.numbers)
+                    .ok_or(LotteryGameError::InvalidLotteryGameWinner)?;
+
+                if ticket.is_prize_claimed {
+                    return Err(LotteryGameError::PrizeAlreadyClaimed.into());
+                }
+                // every entry of the ticket matched the tier
+                self.tier_results[tier]
+                    .prize_amount
+                    .checked_mul(ticket.quantity)
+                    .ok_or(LotteryGameError::InvalidArithmeticOperation)?
+            }
+        };
+        ticket.is_prize_claimed = true;
+
+        Ok(prize_amount)
+    }
+
+    // Index of the prize tier won by the picked numbers, if any.
+    fn matching_tier(&self, lottery_game: &LotteryGame, numbers: &[u8]) -> Option<usize> {
+        let matches = numbers
+            .iter()
+            .filter(|number| self.winning_numbers.contains(number))
+            .count();
+
+        lottery_game
+            .prize_tiers
+            .iter()
+            .position(|prize_tier| prize_tier.matches as usize == matches)
+    }
+}
+
+// Draw `count` distinct offsets below `population` from the randomness, in draw order.
+pub fn draw_without_replacement(randomness: &[u8; 32], count: u64, population: u64) -> Vec<u64> {
+    // offsets drawn so far, kept sorted
+    let mut taken: Vec<u64> = Vec::new();
+    let mut offsets: Vec<u64> = Vec::new();
+
+    for draw_index in 0..count.min(population) {
+        // pick among the offsets not drawn yet, then map the pick back to its offset
+        let mut offset = random_u64(randomness, draw_index) % (population - draw_index);
+        for taken_offset in taken.iter() {
+            if *taken_offset <= offset {
+                offset += 1;
+            } else {
+                break;
+            }
+        }
+        let position = taken.partition_point(|taken_offset| *taken_offset < offset);
+        taken.insert(position, offset);
+        offsets.push(offset);
+    }
+
+    offsets
+}
+
+// Draw `pick_count` distinct numbers between 1 and `number_range`, sorted ascending.
+pub fn draw_numbers(randomness: &[u8; 32], pick_count: u8, number_range: u8) -> Vec<u8> {
+    let mut numbers: Vec<u8> =
+        draw_without_replacement(randomness, pick_count as u64, number_range as u64)
+            .iter()
+            .map(|offset| *offset as u8 + 1)
+            .collect();
+    numbers.sort_unstable();
+    numbers
+}
+
+// Derive an independent random value for each draw from the same randomness.
+fn random_u64(randomness: &[u8; 32], draw_index: u64) -> u64 {
+    let hash = hashv(&[randomness, &draw_index.to_le_bytes()]).to_bytes();
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&hash[..8]);
+    u64::from_le_bytes(value)
+}
+
+pub fn apply_basis_points(amount: u64, basis_points: u32) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(basis_points as u128)
+        .ok_or(LotteryGameError::InvalidArithmeticOperation)?
+        / BASIS_POINTS as u128;
+
+    u64::try_from(value).map_err(|_| LotteryGameError::InvalidArithmeticOperation.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::state::prize_tier::PrizeTier,
+        solana_program::{
+            entrypoint::SUCCESS,
+            program_stubs::{set_syscall_stubs, SyscallStubs},
+        },
+        std::collections::HashSet,
+    };
+
+    // off-chain there is no clock sysvar, the draw only needs one to timestamp itself
+    struct ClockStub;
+
+    impl SyscallStubs for ClockStub {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Clock) = Clock::default() };
+            SUCCESS
+        }
+    }
+
+    // accounts with every field zeroed, i.e the first variant of every enum and empty vecs
+    fn zeroed<T: AnchorDeserialize>(space: usize) -> T {
+        T::deserialize(&mut &vec![0u8; space][..]).unwrap()
+    }
+
+    fn randomness(seed: u8) -> [u8; 32] {
+        hashv(&[&[seed]]).to_bytes()
+    }
+
+    // raffle whose prize tiers draw the given winners for the given shares (basis points)
+    fn raffle(prize_tiers: &[(u16, u32)]) -> (LotteryGame, LotteryRound) {
+        let mut lottery_game: LotteryGame = zeroed(LotteryGame::INIT_SPACE);
+        lottery_game.game_mode = GameMode::Raffle;
+        lottery_game.prize_tiers = prize_tiers
+            .iter()
+            .map(|(winners, prize_percentage)| PrizeTier {
+                winners: *winners,
+                matches: 0,
+                prize_percentage: *prize_percentage,
+            })
+            .collect();
+        (lottery_game, zeroed(LotteryRound::INIT_SPACE))
+    }
+
+    #[test]
+    fn draw_without_replacement_draws_distinct_offsets_in_range() {
+        for seed in 0..32 {
+            for (count, population) in [(1, 1), (3, 10), (10, 10), (12, 10), (50, 1_000)] {
+                let offsets = draw_without_replacement(&randomness(seed), count, population);
+
+                assert_eq!(offsets.len() as u64, count.min(population));
+                assert!(offsets.iter().all(|offset| *offset < population));
+                let distinct: HashSet<u64> = offsets.iter().copied().collect();
+                assert_eq!(distinct.len(), offsets.len());
+            }
+        }
+    }
+
+    #[test]
+    fn draw_without_replacement_draws_every_offset_when_all_win() {
+        for seed in 0..32 {
+            let mut offsets = draw_without_replacement(&randomness(seed), 20, 20);
+            offsets.sort_unstable();
+            assert_eq!(offsets, (0..20).collect::<Vec<u64>>());
+        }
+    }
+
+    #[test]
+    fn draw_numbers_picks_distinct_sorted_numbers_in_range() {
+        for seed in 0..32 {
+            for (pick_count, number_range) in [(1, 1), (3, 10), (6, 49), (10, 10)] {
+                let numbers = draw_numbers(&randomness(seed), pick_count, number_range);
+
+                assert_eq!(numbers.len(), pick_count as usize);
+                assert!(numbers
+                    .iter()
+                    .all(|number| (1..=number_range).contains(number)));
+                assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn random_u64_is_independent_per_draw_index() {
+        let randomness = randomness(0);
+        let values: HashSet<u64> = (0..100)
+            .map(|index| random_u64(&randomness, index))
+            .collect();
+
+        assert_eq!(values.len(), 100);
+        assert_eq!(random_u64(&randomness, 7), random_u64(&randomness, 7));
+    }
+
+    #[test]
+    fn draw_winning_tickets_draws_each_tier_its_winners() {
+        let (lottery_game, mut lottery_round) = raffle(&[(1, 5_000), (2, 3_000), (3, 2_000)]);
+        lottery_round.first_ticket_number = 10;
+        lottery_round.tickets_sold = 50;
+
+        for seed in 0..32 {
+            lottery_round
+                .draw_winning_tickets(&lottery_game, &randomness(seed))
+                .unwrap();
+
+            let tiers: Vec<u8> = lottery_round
+                .winning_tickets
+                .iter()
+                .map(|winning_ticket| winning_ticket.tier)
+                .collect();
+            assert_eq!(tiers, vec![0, 1, 1, 2, 2, 2]);
+            let ticket_numbers: HashSet<u64> = lottery_round
+                .winning_tickets
+                .iter()
+                .map(|winning_ticket| winning_ticket.ticket_number)
+                .collect();
+            assert_eq!(ticket_numbers.len(), 6);
+            assert!(ticket_numbers
+                .iter()
+                .all(|number| (10..60).contains(number)));
+        }
+    }
+
+    #[test]
+    fn draw_winning_tickets_with_as_many_tickets_as_winners() {
+        let (lottery_game, mut lottery_round) = raffle(&[(1, 5_000), (2, 3_000), (3, 2_000)]);
+        lottery_round.first_ticket_number = 10;
+        lottery_round.tickets_sold = 6;
+
+        lottery_round
+            .draw_winning_tickets(&lottery_game, &randomness(0))
+            .unwrap();
+
+        let mut ticket_numbers: Vec<u64> = lottery_round
+            .winning_tickets
+            .iter()
+            .map(|winning_ticket| winning_ticket.ticket_number)
+            .collect();
+        ticket_numbers.sort_unstable();
+        assert_eq!(ticket_numbers, (10..16).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn draw_winning_tickets_with_fewer_tickets_than_winners() {
+        let (lottery_game, mut lottery_round) = raffle(&[(1, 5_000), (2, 3_000), (3, 2_000)]);
+        lottery_round.tickets_sold = 2;
+
+        lottery_round
+            .draw_winning_tickets(&lottery_game, &randomness(0))
+            .unwrap();
+
+        // the later tiers go without winners
+        let tiers: Vec<u8> = lottery_round
+            .winning_tickets
+            .iter()
+            .map(|winning_ticket| winning_ticket.tier)
+            .collect();
+        assert_eq!(tiers, vec![0, 1]);
+    }
+
+    #[test]
+    fn matching_tier_picks_the_tier_of_the_matched_count() {
+        let mut lottery_game: LotteryGame = zeroed(LotteryGame::INIT_SPACE);
+        lottery_game.game_mode = GameMode::PickNumbers;
+        lottery_game.prize_tiers = [3, 2]
+            .iter()
+            .map(|matches| PrizeTier {
+                winners: 0,
+                matches: *matches,
+                prize_percentage: 5_000,
+            })
+            .collect();
+        let mut lottery_round: LotteryRound = zeroed(LotteryRound::INIT_SPACE);
+        lottery_round.winning_numbers = vec![1, 3, 7];
+
+        assert_eq!(
+            lottery_round.matching_tier(&lottery_game, &[7, 1, 3]),
+            Some(0)
+        );
+        assert_eq!(
+            lottery_round.matching_tier(&lottery_game, &[1, 3, 8]),
+            Some(1)
+        );
+        assert_eq!(lottery_round.matching_tier(&lottery_game, &[1, 8, 9]), None);
+        assert_eq!(lottery_round.matching_tier(&lottery_game, &[2, 4, 5]), None);
+    }
+
+    #[test]
+    fn apply_basis_points_rounds_down() {
+        assert_eq!(apply_basis_points(999, 3_333).unwrap(), 332);
+        assert_eq!(apply_basis_points(1, 9_999).unwrap(), 0);
+        assert_eq!(apply_basis_points(0, 5_000).unwrap(), 0);
+        assert_eq!(apply_basis_points(1_234_567, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn apply_basis_points_keeps_the_whole_amount_at_10_000() {
+        for amount in [1, 7, 999, 1_000_000_001, u64::MAX] {
+            assert_eq!(apply_basis_points(amount, BASIS_POINTS).unwrap(), amount);
+        }
+        // more than the whole amount doesn't fit a u64 any more
+        assert!(apply_basis_points(u64::MAX, BASIS_POINTS + 1).is_err());
+    }
+
+    #[test]
+    fn settle_draw_splits_the_whole_pool() {
+        set_syscall_stubs(Box::new(ClockStub));
+
+        // ticket sales, operator fee, protocol fee, prize share, reserve share, rollover
+        for (amounts_raised, operator_fee, protocol_fee, winning, reserve, rollover) in [
+            (1_000_000_007, 250, 125, 9_000, 5_000, 0),
+            (999_999_999, 333, 77, 6_667, 3_333, 12_345),
+            (101, 1, 1, 1, 9_999, 0),
+            (7, 0, 0, 10_000, 10_000, 3),
+            (1_000_003, 10_000, 0, 5_000, 5_000, 0),
+            (u32::MAX as u64 * 3, 0, 0, 0, 10_000, 1),
+        ] {
+            let (mut lottery_game, mut lottery_round) = raffle(&[(1, BASIS_POINTS)]);
+            lottery_game.lottery_winning_percentage = winning;
+            lottery_game.reserve_percentage = reserve;
+            lottery_round.tickets_sold = 10;
+            lottery_round.amounts_raised = amounts_raised;
+            lottery_round.operator_fees = apply_basis_points(amounts_raised, operator_fee).unwrap();
+            lottery_round.protocol_fees = apply_basis_points(amounts_raised, protocol_fee).unwrap();
+            lottery_round.rollover_amount = rollover;
+
+            lottery_round
+                .settle_draw(&mut lottery_game, randomness(0))
+                .unwrap();
+
+            // every base unit of the ticket sales ends up in exactly one share
+            let shares = lottery_round.operator_fees
+                + lottery_round.protocol_fees
+                + (lottery_round.prize_amount - rollover)
+                + lottery_round.operator_revenue_amount
+                + lottery_round.reserve_amount;
+            assert_eq!(shares, amounts_raised);
+            assert_eq!(lottery_round.available_funds, lottery_round.prize_amount);
+            assert_eq!(
+                lottery_game.operator_fees_available,
+                lottery_round.operator_fees
+            );
+            assert_eq!(
+                lottery_game.protocol_fees_available,
+                lottery_round.protocol_fees
+            );
+            assert_eq!(lottery_round.status, LotteryRoundStatus::Closed);
+        }
+    }
+
+    #[test]
+    fn settle_draw_pays_the_whole_prize_at_10_000() {
+        set_syscall_stubs(Box::new(ClockStub));
+
+        let (mut lottery_game, mut lottery_round) = raffle(&[(1, BASIS_POINTS)]);
+        lottery_game.lottery_winning_percentage = BASIS_POINTS;
+        lottery_game.reserve_percentage = BASIS_POINTS;
+        lottery_round.tickets_sold = 3;
+        lottery_round.amounts_raised = 1_000_000_001;
+        lottery_round.rollover_amount = 99;
+
+        lottery_round
+            .settle_draw(&mut lottery_game, randomness(0))
+            .unwrap();
+
+        assert_eq!(lottery_round.prize_amount, 1_000_000_100);
+        assert_eq!(lottery_round.operator_revenue_amount, 0);
+        assert_eq!(lottery_round.reserve_amount, 0);
+        assert_eq!(lottery_round.winning_tickets[0].prize_amount, 1_000_000_100);
+    }
+}
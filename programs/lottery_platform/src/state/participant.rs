@@ -9,6 +9,6 @@ pub struct Participant {
     #[max_len(3)]
     pub country: String, // home country of participant
     pub active: bool,  // status of participant
-    pub total_units_lottery_ticket: u32, // total units of lottery game tickets bought by participant
-    pub available_funds: u32, // available funds equal to the lottery game owned by participant
+    pub total_units_lottery_ticket: u64, // number of lottery tickets bought by participant, across all lottery games
+    pub total_amount_spent: u64, // amount paid for those tickets (base units), across all lottery games
 }
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// most prize tiers a lottery game can configure
+pub const MAX_PRIZE_TIERS: usize = 5;
+// most winning tickets a lottery round can draw across all of its prize tiers
+pub const MAX_WINNERS: usize = 50;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct PrizeTier {
+    pub winners: u16,          // number of winning tickets drawn for the tier (raffle)
+    pub matches: u8,           // numbers a ticket has to match to win the tier (pick numbers)
+    pub prize_percentage: u32, // share of the round's prize pool split among the tier's winners (basis points)
+}
@@ -0,0 +1,18 @@
+use crate::state::lottery_game::MAX_PICK_COUNT;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Ticket {
+    pub owner: Pubkey,        // publickey of the participant who bought the ticket
+    pub lottery_game: Pubkey, // lottery game the ticket was bought for
+    pub ticket_number: u64, // sequential ticket number of the ticket's first entry within the lottery game
+    pub quantity: u64,      // entries held by the ticket, numbered on from ticket_number
+    pub round_number: u64,  // lottery round the ticket takes part in
+    pub amount_paid: u64, // amount paid for all the ticket's entries, refunded if the round can't be drawn
+    #[max_len(MAX_PICK_COUNT)]
+    pub numbers: Vec<u8>, // numbers picked by every entry of the ticket, sorted ascending (pick numbers)
+    pub is_prize_claimed: bool, // prizes of the ticket's winning entries have been withdrawn
+    pub is_refunded: bool,      // ticket's amount paid has been refunded
+    pub is_initialized: bool,
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MockOracleError {
+    #[msg("Invalid oracle authority.")]
+    InvalidOracleAuthority,
+    #[msg("Signer is not the program's upgrade authority.")]
+    InvalidUpgradeAuthority,
+
+    // account
+    #[msg("Account is not initialized.")]
+    AccountNotInitialized,
+    #[msg("Account is already initialized.")]
+    AccountAlreadyInitialized,
+}
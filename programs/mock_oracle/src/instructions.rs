@@ -0,0 +1,8 @@
+// admin instructions
+pub mod init;
+
+// oracle instructions
+pub mod fulfil_randomness;
+
+// bring everything in scope
+pub use {fulfil_randomness::*, init::*};
@@ -0,0 +1,75 @@
+//! FulfilRandomness instruction handler
+
+use {
+    crate::{error::MockOracleError, state::OracleConfigs},
+    anchor_lang::prelude::*,
+    lottery_platform::{
+        cpi::{accounts::FulfilLotteryGameRandomness, fulfil_lottery_game_randomness},
+        instructions::FulfilLotteryGameRandomnessParams,
+        program::LotteryPlatform,
+        state::{
+            configs::LotteryGameConfigs, lottery_game::LotteryGame, lottery_round::LotteryRound,
+        },
+    },
+    solana_program::{hash::hashv, sysvar::clock::Clock},
+};
+
+#[derive(Accounts)]
+pub struct FulfilRandomness<'info> {
+    #[account(has_one = authority @ MockOracleError::InvalidOracleAuthority,
+        constraint = oracle_configs.is_initialized @ MockOracleError::AccountNotInitialized,
+        seeds = [b"oracle-configs"],
+        bump
+    )]
+    pub oracle_configs: Account<'info, OracleConfigs>,
+    #[account(seeds = [b"oracle-authority"], bump = oracle_configs.oracle_signer_bump)]
+    /// CHECK: signing PDA, holds no data.
+    pub oracle_signer: UncheckedAccount<'info>,
+    pub lottery_game_configs: Account<'info, LotteryGameConfigs>,
+    pub lottery_game: Account<'info, LotteryGame>,
+    #[account(mut)]
+    pub lottery_round: Account<'info, LotteryRound>,
+    pub authority: Signer<'info>,
+    pub lottery_platform_program: Program<'info, LotteryPlatform>,
+}
+
+pub fn fulfil_randomness(ctx: Context<FulfilRandomness>) -> Result<()> {
+    let lottery_round = &ctx.accounts.lottery_round;
+    let request_id = lottery_round.randomness_request_id;
+
+    // Mock randomness only, a real oracle would deliver a verifiable proof here
+    let clock = Clock::get()?;
+    let randomness = hashv(&[
+        lottery_round.key().as_ref(),
+        &request_id.to_le_bytes(),
+        &clock.slot.to_le_bytes(),
+    ])
+    .to_bytes();
+
+    let seeds = &[
+        b"oracle-authority".as_ref(),
+        &[ctx.accounts.oracle_configs.oracle_signer_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi = CpiContext::new_with_signer(
+        ctx.accounts.lottery_platform_program.to_account_info(),
+        FulfilLotteryGameRandomness {
+            lottery_game_configs: ctx.accounts.lottery_game_configs.to_account_info(),
+            lottery_game: ctx.accounts.lottery_game.to_account_info(),
+            lottery_round: ctx.accounts.lottery_round.to_account_info(),
+            oracle_authority: ctx.accounts.oracle_signer.to_account_info(),
+        },
+        signer,
+    );
+
+    fulfil_lottery_game_randomness(
+        cpi,
+        FulfilLotteryGameRandomnessParams {
+            request_id,
+            randomness,
+        },
+    )?;
+
+    Ok(())
+}
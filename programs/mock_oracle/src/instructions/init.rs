@@ -0,0 +1,55 @@
+//! Init instruction handler
+
+use {
+    crate::{error::MockOracleError, program::MockOracle, state::OracleConfigs},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: InitParams)]
+pub struct Init<'info> {
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OracleConfigs::INIT_SPACE,
+        constraint = !oracle_configs.is_initialized @ MockOracleError::AccountAlreadyInitialized,
+        seeds = [b"oracle-configs"],
+        bump
+    )]
+    pub oracle_configs: Account<'info, OracleConfigs>,
+    #[account(seeds = [b"oracle-authority"], bump)]
+    /// CHECK: signing PDA, holds no data.
+    pub oracle_signer: UncheckedAccount<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, MockOracle>,
+    // only the program's upgrade authority can initialize the oracle and pick its authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(owner.key()) @ MockOracleError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitParams {
+    pub authority: Pubkey, // publickey allowed to fulfil randomness requests
+}
+
+pub fn init(ctx: Context<Init>, params: &InitParams) -> Result<()> {
+    if params.authority == Pubkey::default() {
+        return Err(MockOracleError::InvalidOracleAuthority.into());
+    }
+
+    let oracle_configs = &mut ctx.accounts.oracle_configs;
+
+    oracle_configs.authority = params.authority;
+    oracle_configs.oracle_signer_bump = ctx.bumps.oracle_signer;
+    oracle_configs.is_initialized = true;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! mock_oracle program entrypoint
+//!
+//! A stand-in for a VRF-style randomness oracle. It signs lottery_platform
+//! randomness fulfilments with its `oracle-authority` PDA so the oracle draw
+//! flow can be exercised against a local validator.
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use {anchor_lang::prelude::*, instructions::*};
+
+declare_id!("2bb1eeVEx6RkPieftpcHeGc6SqzuXtUVC76qW95kk8yZ");
+
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    // admin instructions
+    pub fn init(ctx: Context<Init>, params: InitParams) -> Result<()> {
+        instructions::init(ctx, &params)
+    }
+
+    // oracle instructions
+    pub fn fulfil_randomness(ctx: Context<FulfilRandomness>) -> Result<()> {
+        instructions::fulfil_randomness(ctx)
+    }
+}
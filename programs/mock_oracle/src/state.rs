@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct OracleConfigs {
+    pub authority: Pubkey,      // publickey allowed to fulfil randomness requests
+    pub oracle_signer_bump: u8, // bump of the oracle-authority PDA
+    pub is_initialized: bool,
+}
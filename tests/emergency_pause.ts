@@ -0,0 +1,355 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitUntil,
+  eventsOf,
+  grantRoles,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("emergency_pause", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+  // guardian authority able to pause the platform and lottery games
+  const guardian = anchor.web3.Keypair.generate();
+
+  const ticketPrice = anchor.web3.LAMPORTS_PER_SOL; // 1 sol in lamports
+
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  // reason codes are defined off-chain by the guardians
+  const suspiciousSales = 1;
+  const oracleOutage = 2;
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+  const gameEntry = gameEntryAddress(lotteryGame, participant);
+
+  const buy = (ticketNumber: number) =>
+    buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        lotteryGame,
+        lotteryRound,
+        ticketNumber,
+        { treasuryVault }
+      ),
+      ticketPrice
+    );
+
+  const getWinner = () =>
+    program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Pause",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner]);
+
+  const setPlatformPaused = (paused: boolean, reasonCode: number) =>
+    program.methods
+      .setPlatformPaused({ paused: paused, reasonCode: reasonCode })
+      .accounts({
+        owner: guardian.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .signers([guardian]);
+
+  const setLotteryGamePaused = (
+    paused: boolean,
+    reasonCode: number,
+    owner: anchor.web3.Keypair = guardian
+  ) =>
+    program.methods
+      .setLotteryGamePaused({ paused: paused, reasonCode: reasonCode })
+      .accounts({
+        owner: owner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+      })
+      .signers([owner]);
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    await grantRoles(guardian.publicKey, [{ guardian: {} }]);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Pause",
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(ticketPrice),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    await buy(0).rpc();
+  });
+
+  after(async () => {
+    // the configs are shared, leave the platform unpaused for other suites
+    const configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    if (configs.paused) {
+      await setPlatformPaused(false, 0).rpc();
+    }
+  });
+
+  it("Is reject pausing by anyone but a guardian!", async () => {
+    try {
+      await setLotteryGamePaused(
+        true,
+        suspiciousSales,
+        lotteryGameOwner
+      ).rpc();
+      assert.fail("only guardian authorities can pause lottery games");
+    } catch (error) {
+      assert.include(error.toString(), "MissingGuardianRole");
+    }
+  });
+
+  it("Is pause lottery game sales and draws!", async () => {
+    const signature = await setLotteryGamePaused(true, suspiciousSales).rpc();
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "lotteryGamePauseUpdated");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.ok(event.data.guardian.equals(guardian.publicKey));
+    assert.isTrue(event.data.paused);
+    assert.equal(event.data.reasonCode, suspiciousSales);
+    assert.isAbove(event.data.timestamp.toNumber(), 0);
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.isTrue(game.paused);
+
+    try {
+      await setLotteryGamePaused(true, suspiciousSales).rpc();
+      assert.fail("the lottery game is already paused");
+    } catch (error) {
+      assert.include(error.toString(), "PauseStateUnchanged");
+    }
+
+    try {
+      await buy(1).rpc();
+      assert.fail("tickets of a paused lottery game can't be sold");
+    } catch (error) {
+      assert.include(error.toString(), "LotteryGamePaused");
+    }
+
+    const { drawAt } = await program.account.lotteryRound.fetch(lotteryRound);
+    await waitUntil(drawAt.toNumber() - 1);
+
+    try {
+      await getWinner().rpc();
+      assert.fail("a paused lottery game can't be drawn");
+    } catch (error) {
+      assert.include(error.toString(), "LotteryGamePaused");
+    }
+
+    await setLotteryGamePaused(false, suspiciousSales).rpc();
+  });
+
+  it("Is pause the draws of all lottery games!", async () => {
+    const signature = await setPlatformPaused(true, oracleOutage).rpc();
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "platformPauseUpdated");
+    assert.ok(event.data.guardian.equals(guardian.publicKey));
+    assert.isTrue(event.data.paused);
+    assert.equal(event.data.reasonCode, oracleOutage);
+
+    try {
+      await getWinner().rpc();
+      assert.fail("no lottery game can be drawn while the platform is paused");
+    } catch (error) {
+      assert.include(error.toString(), "PlatformPaused");
+    }
+
+    await setPlatformPaused(false, oracleOutage).rpc();
+
+    const configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.isFalse(configs.paused);
+
+    await getWinner().rpc();
+
+    // a pause must not let anyone void the pending draw
+    await setLotteryGamePaused(true, suspiciousSales).rpc();
+
+    try {
+      await program.methods
+        .expireLotteryGameDraw()
+        .accounts({
+          signer: provider.wallet.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+        })
+        .rpc();
+      assert.fail("a paused draw can't be expired");
+    } catch (error) {
+      assert.include(error.toString(), "LotteryGamePaused");
+    }
+
+    await setLotteryGamePaused(false, suspiciousSales).rpc();
+
+    // the reveal deadline is counted from the unpause, the draw entropy isn't
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    const { drawSlot } = await program.account.lotteryRound.fetch(
+      lotteryRound
+    );
+    assert.isTrue(game.unpausedSlot.gt(drawSlot));
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(round.status, { closed: {} });
+  });
+
+  it("Is claim prizes while paused!", async () => {
+    await setPlatformPaused(true, oracleOutage).rpc();
+    await setLotteryGamePaused(true, suspiciousSales).rpc();
+
+    const balanceBefore = await provider.connection.getBalance(
+      participantOwner.publicKey
+    );
+
+    // the only ticket sold won the jackpot
+    await program.methods
+      .withdrawLotteryGameWinnings()
+      .accounts({
+        owner: participantOwner.publicKey,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        gameEntry: gameEntry,
+        ticket: ticketAddress(lotteryGame, 0),
+        senderTokens: null,
+        recipientTokens: null,
+        mintToken: null,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        tokenProgram: null,
+        associateTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      participantOwner.publicKey
+    );
+    assert.isAbove(balanceAfter, balanceBefore);
+
+    await setPlatformPaused(false, oracleOutage).rpc();
+  });
+});
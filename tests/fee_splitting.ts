@@ -0,0 +1,444 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitUntil,
+  eventsOf,
+  setupLotteryGameOwner,
+  airdrop,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("fee_splitting", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+  const operatorRevenueWallet = anchor.web3.Keypair.generate();
+  const protocolFeeWallet = anchor.web3.Keypair.generate();
+
+  const ticketPrice = anchor.web3.LAMPORTS_PER_SOL; // 1 sol in lamports
+  const ticketsBought = 2;
+  const amountPaid = ticketsBought * ticketPrice;
+  const operatorFee = (amountPaid * 500) / 10000; // 5 % operator fee
+  const protocolFee = (amountPaid * 100) / 10000; // 1 % protocol fee
+  const prizePoolAmount = amountPaid - operatorFee - protocolFee;
+
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+  const ticket = ticketAddress(lotteryGame, 0);
+
+  const updateFeeConfigs = (
+    operatorFeePercentage: number,
+    protocolFeePercentage: number,
+    protocolFeeAccount: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey = payer.publicKey
+  ) =>
+    program.methods
+      .updateFeeConfigs({
+        operatorFeePercentage: operatorFeePercentage,
+        protocolFeePercentage: protocolFeePercentage,
+        protocolFeeAccount: protocolFeeAccount,
+      })
+      .accounts({
+        owner: owner,
+        lotteryGameConfigs: lotteryGameConfigs,
+      });
+
+  const withdrawOperatorFeesAccounts = {
+    owner: lotteryGameOwner.publicKey,
+    lotteryGameConfigs: lotteryGameConfigs,
+    lotteryGame: lotteryGame,
+    senderTokens: null,
+    operatorFeeTokens: null,
+    mintToken: null,
+    operatorFeeWallet: operatorRevenueWallet.publicKey,
+    depositAccount: depositAccount.publicKey,
+    pdaAuth: pdaAuth,
+    treasuryVault: treasuryVault,
+    tokenProgram: null,
+    systemProgram: anchor.web3.SystemProgram.programId,
+  };
+
+  const withdrawProtocolFeesAccounts = (owner: anchor.web3.PublicKey) => ({
+    owner: owner,
+    lotteryGameConfigs: lotteryGameConfigs,
+    lotteryGame: lotteryGame,
+    senderTokens: null,
+    protocolFeeTokens: null,
+    mintToken: null,
+    protocolFeeWallet: protocolFeeWallet.publicKey,
+    depositAccount: depositAccount.publicKey,
+    pdaAuth: pdaAuth,
+    treasuryVault: treasuryVault,
+    tokenProgram: null,
+    systemProgram: anchor.web3.SystemProgram.programId,
+  });
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+  });
+
+  after(async () => {
+    // lottery games of the other suites are registered without fees
+    await updateFeeConfigs(0, 0, anchor.web3.PublicKey.default).rpc();
+  });
+
+  it("Is configure the platform fees!", async () => {
+    // only the super admin can configure the fees
+    try {
+      await updateFeeConfigs(
+        200,
+        100,
+        protocolFeeWallet.publicKey,
+        lotteryGameOwner.publicKey
+      )
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("only the super admin can configure the fees");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSuperAdmin");
+    }
+
+    // the fees can't exceed the whole ticket price
+    try {
+      await updateFeeConfigs(9000, 1001, protocolFeeWallet.publicKey).rpc();
+      assert.fail("fees above 100 % should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidPercentage");
+    }
+
+    // 2 % default operator fee, 1 % protocol fee
+    await updateFeeConfigs(200, 100, protocolFeeWallet.publicKey).rpc();
+
+    const configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.equal(configs.operatorFeePercentage, 200);
+    assert.equal(configs.protocolFeePercentage, 100);
+    assert.ok(configs.protocolFeeAccount.equals(protocolFeeWallet.publicKey));
+  });
+
+  it("Is register a lottery game with its own operator fee!", async () => {
+    const signature = await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Fees",
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: 500, // 5 %, instead of the platform's 2 %
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: operatorRevenueWallet.publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(ticketPrice),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    // the protocol fee is always the platform's
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.operatorFeePercentage, 500);
+    assert.equal(game.protocolFeePercentage, 100);
+    assert.ok(game.protocolFeeAccount.equals(protocolFeeWallet.publicKey));
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "gameRegistered");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.ok(event.data.operator.equals(lotteryOperator));
+    assert.equal(event.data.country, "KE");
+    assert.equal(event.data.unitCostOfLotteryTicket.toNumber(), ticketPrice);
+    assert.equal(event.data.operatorFeePercentage, 500);
+    assert.equal(event.data.protocolFeePercentage, 100);
+  });
+
+  it("Is split every purchase into prize pool and fees!", async () => {
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    const signature = await buyTicket(
+      participantOwner,
+      buyAccounts(participantOwner.publicKey, lotteryGame, lotteryRound, 0, {
+        treasuryVault,
+      }),
+      amountPaid,
+      ticketsBought
+    ).rpc();
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.totalAmountsRaised.toNumber(), amountPaid);
+    assert.equal(game.totalPrizePoolAmounts.toNumber(), prizePoolAmount);
+    assert.equal(game.totalOperatorFees.toNumber(), operatorFee);
+    assert.equal(game.totalProtocolFees.toNumber(), protocolFee);
+
+    // fees are held in the round until it is drawn
+    assert.equal(game.operatorFeesAvailable.toNumber(), 0);
+    assert.equal(game.protocolFeesAvailable.toNumber(), 0);
+
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.equal(round.operatorFees.toNumber(), operatorFee);
+    assert.equal(round.protocolFees.toNumber(), protocolFee);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "ticketPurchased");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.ok(event.data.owner.equals(participantOwner.publicKey));
+    assert.ok(event.data.ticket.equals(ticket));
+    assert.equal(event.data.quantity.toNumber(), ticketsBought);
+    assert.equal(event.data.amount.toNumber(), amountPaid);
+    assert.equal(event.data.prizePoolAmount.toNumber(), prizePoolAmount);
+    assert.equal(event.data.operatorFee.toNumber(), operatorFee);
+    assert.equal(event.data.protocolFee.toNumber(), protocolFee);
+
+    try {
+      await program.methods
+        .withdrawOperatorFees()
+        .accounts(withdrawOperatorFeesAccounts)
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("fees of an undrawn round can't be withdrawn");
+    } catch (error) {
+      assert.include(error.toString(), "NoFeesToWithdraw");
+    }
+  });
+
+  it("Is release the fees once the round is drawn!", async () => {
+    const { drawAt } = await program.account.lotteryRound.fetch(lotteryRound);
+    await waitUntil(drawAt.toNumber() - 1);
+
+    const requested = await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Fees",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const [drawRequested] = await eventsOf(requested);
+    assert.equal(drawRequested.name, "drawRequested");
+    assert.ok(drawRequested.data.lotteryGame.equals(lotteryGame));
+    assert.equal(drawRequested.data.roundNumber.toNumber(), 0);
+    assert.deepEqual(drawRequested.data.drawMethod, { commitReveal: {} });
+    assert.equal(drawRequested.data.ticketsSold.toNumber(), ticketsBought);
+    assert.equal(drawRequested.data.amountsRaised.toNumber(), amountPaid);
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    const signature = await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the prize comes out of the ticket sales net of fees
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.equal(
+      round.prizeAmount.toNumber(),
+      (prizePoolAmount * 9000) / 10000
+    );
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.operatorFeesAvailable.toNumber(), operatorFee);
+    assert.equal(game.protocolFeesAvailable.toNumber(), protocolFee);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "winnerDrawn");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.winningTickets.length, 1);
+    assert.equal(
+      event.data.prizeAmount.toNumber(),
+      round.prizeAmount.toNumber()
+    );
+    assert.equal(event.data.operatorFees.toNumber(), operatorFee);
+    assert.equal(event.data.protocolFees.toNumber(), protocolFee);
+    assert.equal(event.data.drawnAt.toNumber(), round.drawnAt.toNumber());
+
+  it("Is withdraw the operator fees!", async () => {
+    const signature = await program.methods
+      .withdrawOperatorFees()
+      .accounts(withdrawOperatorFeesAccounts)
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const balance = await provider.connection.getBalance(
+      operatorRevenueWallet.publicKey
+    );
+    assert.equal(balance, operatorFee);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "operatorFeesWithdrawn");
+    assert.ok(event.data.recipient.equals(operatorRevenueWallet.publicKey));
+    assert.equal(event.data.amount.toNumber(), operatorFee);
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.operatorFeesAvailable.toNumber(), 0);
+    assert.equal(
+      game.totalAvailableFunds.toNumber(),
+      amountPaid - operatorFee
+    );
+  });
+
+  it("Is withdraw the protocol fees!", async () => {
+    // only the super admin can withdraw the protocol fees
+    try {
+      await program.methods
+        .withdrawProtocolFees()
+        .accounts(withdrawProtocolFeesAccounts(lotteryGameOwner.publicKey))
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("only the super admin can withdraw the protocol fees");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSuperAdmin");
+    }
+
+    const signature = await program.methods
+      .withdrawProtocolFees()
+      .accounts(withdrawProtocolFeesAccounts(payer.publicKey))
+      .rpc();
+
+    const balance = await provider.connection.getBalance(
+      protocolFeeWallet.publicKey
+    );
+    assert.equal(balance, protocolFee);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "protocolFeesWithdrawn");
+    assert.ok(event.data.recipient.equals(protocolFeeWallet.publicKey));
+    assert.equal(event.data.amount.toNumber(), protocolFee);
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.protocolFeesAvailable.toNumber(), 0);
+    assert.equal(
+      game.totalAvailableFunds.toNumber(),
+      amountPaid - operatorFee - protocolFee
+    );
+  });
+
+  it("Is distribute the round proceeds!", async () => {
+    // the operator revenue is the prize pool left once the prize is set aside
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    const operatorRevenue = round.operatorRevenueAmount.toNumber();
+    assert.equal(
+      operatorRevenue,
+      prizePoolAmount - round.prizeAmount.toNumber()
+    );
+
+    const signature = await program.methods
+      .distributeLotteryRoundProceeds()
+      .accounts({
+        signer: payer.publicKey,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        senderTokens: null,
+        operatorRevenueTokens: null,
+        reserveTokens: null,
+        mintToken: null,
+        operatorRevenueWallet: operatorRevenueWallet.publicKey,
+        reserveWallet: null,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const balance = await provider.connection.getBalance(
+      operatorRevenueWallet.publicKey
+    );
+    assert.equal(balance, operatorFee + operatorRevenue);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "roundProceedsDistributed");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 0);
+    assert.equal(event.data.operatorRevenueAmount.toNumber(), operatorRevenue);
+    assert.equal(event.data.reserveAmount.toNumber(), 0);
+  });
+});
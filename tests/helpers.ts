@@ -0,0 +1,241 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+
+// helpers shared by the test suites, which all run against the same local
+// validator and lottery game configs
+const provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+// the provider wallet is the program's upgrade authority, hence the super admin
+const payer = (provider.wallet as anchor.Wallet).payer;
+
+const address = (seeds: (Buffer | Uint8Array)[]) =>
+  anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+const seed = (name: string) => anchor.utils.bytes.utf8.encode(name);
+const u64 = (value: number) =>
+  new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+
+export const associateTokenProgram = new anchor.web3.PublicKey(
+  "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+);
+
+export const lotteryGameConfigs = address([seed("lottery-game-configs")]);
+// only the upgrade authority (the provider wallet) can initialize the configs
+export const programData = anchor.web3.PublicKey.findProgramAddressSync(
+  [program.programId.toBuffer()],
+  new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+)[0];
+
+// program addresses of the accounts a lottery game is made of
+export const pdaAuthAddress = (depositAccount: anchor.web3.PublicKey) =>
+  address([seed("auth"), depositAccount.toBuffer()]);
+export const treasuryVaultAddress = (pdaAuth: anchor.web3.PublicKey) =>
+  address([seed("treasury-vault"), pdaAuth.toBuffer()]);
+export const lotteryOperatorAddress = (authority: anchor.web3.PublicKey) =>
+  address([seed("lottery-operator"), authority.toBuffer()]);
+export const lotteryGameCounterAddress = (owner: anchor.web3.PublicKey) =>
+  address([seed("lottery-game-counter"), owner.toBuffer()]);
+export const lotteryGameAddress = (
+  owner: anchor.web3.PublicKey,
+  gameId: number
+) => address([seed("lottery-game"), owner.toBuffer(), u64(gameId)]);
+export const vaultTokensAddress = (lotteryGame: anchor.web3.PublicKey) =>
+  address([seed("vault-tokens"), lotteryGame.toBuffer()]);
+export const lotteryRoundAddress = (
+  lotteryGame: anchor.web3.PublicKey,
+  roundNumber: number
+) => address([seed("lottery-round"), lotteryGame.toBuffer(), u64(roundNumber)]);
+export const participantAddress = (owner: anchor.web3.PublicKey) =>
+  address([seed("participant"), owner.toBuffer()]);
+// entry of a participant in a lottery game
+export const gameEntryAddress = (
+  lotteryGame: anchor.web3.PublicKey,
+  participant: anchor.web3.PublicKey
+) =>
+  address([seed("game-entry"), lotteryGame.toBuffer(), participant.toBuffer()]);
+export const ticketAddress = (
+  lotteryGame: anchor.web3.PublicKey,
+  ticketNumber: number
+) => address([seed("ticket"), lotteryGame.toBuffer(), u64(ticketNumber)]);
+
+// fund the keypairs from the local validator's faucet
+export const airdrop = async (
+  keypairs: anchor.web3.Keypair[],
+  lamports = 10 * anchor.web3.LAMPORTS_PER_SOL
+) => {
+  for (const keypair of keypairs) {
+    const signature = await provider.connection.requestAirdrop(
+      keypair.publicKey,
+      lamports
+    );
+    const latestBlockHash = await provider.connection.getLatestBlockhash();
+
+    await provider.connection.confirmTransaction({
+      blockhash: latestBlockHash.blockhash,
+      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+      signature: signature,
+    });
+  }
+};
+
+// accounts of a ticket purchase; sol lottery games pay into the treasury vault,
+// token lottery games from the buyer's token account into the game's vault
+export const buyAccounts = (
+  buyer: anchor.web3.PublicKey,
+  lotteryGame: anchor.web3.PublicKey,
+  lotteryRound: anchor.web3.PublicKey,
+  ticketNumber: number,
+  payment:
+    | { treasuryVault: anchor.web3.PublicKey }
+    | {
+        senderTokens: anchor.web3.PublicKey;
+        mintToken: anchor.web3.PublicKey;
+        tokenProgram?: anchor.web3.PublicKey;
+      }
+) => {
+  const participant = participantAddress(buyer);
+  const token = "mintToken" in payment;
+
+  return {
+    lotteryGameConfigs: lotteryGameConfigs,
+    owner: buyer,
+    lotteryGame: lotteryGame,
+    lotteryRound: lotteryRound,
+    participant: participant,
+    gameEntry: gameEntryAddress(lotteryGame, participant),
+    ticket: ticketAddress(lotteryGame, ticketNumber),
+    senderTokens: token ? payment.senderTokens : null,
+    recipientTokens: token ? vaultTokensAddress(lotteryGame) : null,
+    mintToken: token ? payment.mintToken : null,
+    treasuryVault: token ? null : payment.treasuryVault,
+    tokenProgram: token
+      ? payment.tokenProgram ?? anchor.utils.token.TOKEN_PROGRAM_ID
+      : null,
+    associateTokenProgram: associateTokenProgram,
+    systemProgram: anchor.web3.SystemProgram.programId,
+  };
+};
+
+// buy `quantity` tickets of a raffle (or of the picked numbers) in one purchase
+export const buyTicket = (
+  buyer: anchor.web3.Keypair,
+  accounts: ReturnType<typeof buyAccounts>,
+  amount: number | anchor.BN,
+  quantity = 1,
+  numbers: number[] = [],
+  quickPick = false
+) =>
+  program.methods
+    .buyLotteryTicket({
+      amount: new anchor.BN(amount),
+      quantity: new anchor.BN(quantity),
+      numbers: numbers,
+      quickPick: quickPick,
+    })
+    .accounts(accounts)
+    .signers([buyer]);
+
+// sales window of a round, sales close and the draw opens `salesPeriod` seconds
+// from now
+export const schedule = (salesPeriod: number) => {
+  const now = Math.floor(Date.now() / 1000);
+  return {
+    salesOpenAt: new anchor.BN(now - 60),
+    salesCloseAt: new anchor.BN(now + salesPeriod),
+    drawAt: new anchor.BN(now + salesPeriod),
+  };
+};
+
+// wait for the validator clock to pass the given unix timestamp
+export const waitUntil = async (timestamp: number) => {
+  while (true) {
+    const slot = await provider.connection.getSlot();
+    const blockTime = await provider.connection.getBlockTime(slot);
+    if (blockTime !== null && blockTime > timestamp) break;
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+};
+
+// wait for the validator clock to reach the round's draw time
+export const waitForDraw = async (round: anchor.web3.PublicKey) => {
+  const { drawAt } = await program.account.lotteryRound.fetch(round);
+  await waitUntil(drawAt.toNumber() - 1);
+};
+
+// events emitted by the program in a confirmed transaction
+export const eventsOf = async (signature: string) => {
+  await provider.connection.confirmTransaction(signature, "confirmed");
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(tx.meta.logMessages));
+};
+
+// lottery game configs may already exist from another suite, either way the
+// provider wallet is the super admin
+export const initConfigs = async () => {
+  try {
+    await program.methods
+      .init()
+      .accounts({
+        owner: payer.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        program: program.programId,
+        programData: programData,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  } catch (error) {
+    if (!error.toString().includes("already in use")) throw error;
+  }
+};
+
+// the super admin lets the authority run and draw lottery games
+export const grantRoles = async (
+  authority: anchor.web3.PublicKey,
+  roles: object[] = [{ operator: {} }, { draw: {} }]
+) => {
+  for (const role of roles) {
+    await program.methods
+      .grantRole({ role: role, authority: authority })
+      .accounts({
+        owner: payer.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .rpc();
+  }
+};
+
+// the super admin licenses the authority as an operator in Kenya
+export const registerLotteryOperator = async (
+  authority: anchor.web3.PublicKey
+) => {
+  const lotteryOperator = lotteryOperatorAddress(authority);
+
+  await program.methods
+    .registerOperator({
+      authority: authority,
+      name: "Company Lotto Ke",
+      licensedCountries: ["KE"],
+    })
+    .accounts({
+      owner: payer.publicKey,
+      lotteryGameConfigs: lotteryGameConfigs,
+      lotteryOperator: lotteryOperator,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+
+  return lotteryOperator;
+};
+
+// configs, roles and operator license every lottery game admin starts with
+export const setupLotteryGameOwner = async (
+  authority: anchor.web3.PublicKey
+) => {
+  await initConfigs();
+  await grantRoles(authority);
+  return registerLotteryOperator(authority);
+};
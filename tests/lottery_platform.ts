@@ -2,11 +2,33 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { LotteryPlatform } from "../target/types/lottery_platform";
 import {
-  Account,
   createAccount,
   getOrCreateAssociatedTokenAccount,
 } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitForDraw,
+  grantRoles,
+  registerLotteryOperator,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  vaultTokensAddress,
+  airdrop,
+  buyAccounts,
+  lotteryOperatorAddress,
+  programData,
+} from "./helpers";
 
 describe("lottery_platform", () => {
   // Configure the client to use the local cluster.
@@ -23,9 +45,6 @@ describe("lottery_platform", () => {
   ); // USDC devnet */
 
   const payer = wallet.payer;
-  const associateTokenProgram = new anchor.web3.PublicKey(
-    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-  );
   const mintToken = anchor.web3.Keypair.generate(); // dummy usdc token created for test purposes
   const tokenAccount = anchor.utils.token.associatedAddress({
     mint: mintToken.publicKey,
@@ -38,113 +57,50 @@ describe("lottery_platform", () => {
   let secondParticipantOwner = anchor.web3.Keypair.generate();
   let secondParticipantOwnerATA = anchor.web3.Keypair.generate();
 
-  let treasuryVaultATA: Account;
+  // token accounts receiving the non-prize share of ticket sales
+  const operatorRevenueATA = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: lotteryGameOwner.publicKey,
+  });
+  const reserveATA = anchor.utils.token.associatedAddress({
+    mint: mintToken.publicKey,
+    owner: adminOwner.publicKey,
+  });
 
-  // pdaAuth
-  let [pdaAuth, adminPdaBump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode("auth"),
-      depositAccount.publicKey.toBuffer(),
-    ],
-    program.programId
-  );
-  let [treasuryVault, adminTreasuryBump] =
-    anchor.web3.PublicKey.findProgramAddressSync(
-      [anchor.utils.bytes.utf8.encode("treasury-vault"), pdaAuth.toBuffer()],
-      program.programId
-    );
+  // secret draw seed, only its sha256 hash is committed at registration
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
 
-  let [lotteryGameConfigs] = anchor.web3.PublicKey.findProgramAddressSync(
-    [anchor.utils.bytes.utf8.encode("lottery-game-configs")],
-    program.programId
-  );
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
 
-  let [lotteryGame] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode("lottery-game"),
-      lotteryGameOwner.publicKey.toBuffer(),
-    ],
-    program.programId
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
   );
 
-  let [firstParticipant] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode("participant"),
-      firstParticipantOwner.publicKey.toBuffer(),
-    ],
-    program.programId
-  );
+  // first lottery game of the admin, i.e game id 0
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
 
-  let [secondParticipant] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode("participant"),
-      secondParticipantOwner.publicKey.toBuffer(),
-    ],
-    program.programId
-  );
+  // vault of the lottery game holding the ticket proceeds
+  const vaultTokens = vaultTokensAddress(lotteryGame);
 
-  // admin owner
-  before(async () => {
-    let res = await provider.connection.requestAirdrop(
-      adminOwner.publicKey,
-      10 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    let latestBlockHash = await provider.connection.getLatestBlockhash();
-
-    await provider.connection.confirmTransaction({
-      blockhash: latestBlockHash.blockhash,
-      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
-      signature: res,
-    });
-  });
+  // first round of the lottery game, i.e round number 0
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
 
-  // first participant owner
-  before(async () => {
-    let res = await provider.connection.requestAirdrop(
-      firstParticipantOwner.publicKey,
-      10 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    let latestBlockHash = await provider.connection.getLatestBlockhash();
-
-    await provider.connection.confirmTransaction({
-      blockhash: latestBlockHash.blockhash,
-      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
-      signature: res,
-    });
-  });
+  const firstParticipant = participantAddress(firstParticipantOwner.publicKey);
 
-  // second participant owner
-  before(async () => {
-    let res = await provider.connection.requestAirdrop(
-      secondParticipantOwner.publicKey,
-      10 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    let latestBlockHash = await provider.connection.getLatestBlockhash();
-
-    await provider.connection.confirmTransaction({
-      blockhash: latestBlockHash.blockhash,
-      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
-      signature: res,
-    });
-  });
+  const secondParticipant = participantAddress(
+    secondParticipantOwner.publicKey
+  );
 
-  // lottery game owner
   before(async () => {
-    let res = await provider.connection.requestAirdrop(
-      lotteryGameOwner.publicKey,
-      10 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    let latestBlockHash = await provider.connection.getLatestBlockhash();
-
-    await provider.connection.confirmTransaction({
-      blockhash: latestBlockHash.blockhash,
-      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
-      signature: res,
-    });
+    await airdrop([
+      adminOwner,
+      firstParticipantOwner,
+      secondParticipantOwner,
+      lotteryGameOwner,
+    ]);
   });
 
   it("Is initialized!", async () => {
@@ -152,17 +108,22 @@ describe("lottery_platform", () => {
       const tx = await program.methods
         .init()
         .accounts({
-          owner: adminOwner.publicKey,
+          owner: payer.publicKey,
           lotteryGameConfigs: lotteryGameConfigs,
+          program: program.programId,
+          programData: programData,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([adminOwner])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
       console.log(error);
     }
 
+    await grantRoles(lotteryGameOwner.publicKey);
+
+    await registerLotteryOperator(lotteryGameOwner.publicKey);
+
     try {
       let result = await program.account.lotteryGameConfigs.fetch(
         lotteryGameConfigs
@@ -173,20 +134,67 @@ describe("lottery_platform", () => {
     }
   });
 
-  it("Is register lottery game!", async () => {
+  it("Is create token!", async () => {
+    console.log("mint token: ", mintToken.publicKey.toBase58());
+    console.log("token account: ", tokenAccount.toBase58());
+
     try {
-      let operator = {
-        operator: "Company Lotto Ke",
+      let initParams = {
+        amount: new anchor.BN(200_000_000_000), // 200 tokens in base units (9 decimals)
+        decimals: 9, // token mint in smallest unit i.e 9 decimals
       };
 
+      const tx = await program.methods
+        .createToken(initParams)
+        .accounts({
+          owner: payer.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenAccount: tokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([mintToken])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is register lottery game!", async () => {
+    try {
       let initParams = {
-        operator: operator,
         country: "KE",
         lotteryGameName: "Lotto Ke 100",
-        lotteryWinningPercentage: 90, // 90 %
-        unitCostOfLotteryTicket: 1, // unit cost of lottery ticket
-        decimals: 9, // token mint in smallest unit i.e 9 decimals
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 5000, // 50 % of the remaining 10 % (basis points)
+        operatorFeePercentage: null, // platform default
+        // jackpot and a runner-up prize, shares of the prize pool (basis points)
+        prizeTiers: [
+          { winners: 1, matches: 0, prizePercentage: 7000 },
+          { winners: 1, matches: 0, prizePercentage: 3000 },
+        ],
+        operatorRevenueAccount: operatorRevenueATA,
+        reserveAccount: reserveATA,
+        unitCostOfLotteryTicket: new anchor.BN(10_000_000_000), // 10 tokens in base units (9 decimals)
+        paymentMode: { token: {} }, // tickets are paid in the token mint
         valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { unwonAndUnclaimed: {} },
+        claimPeriod: new anchor.BN(60 * 60 * 24 * 30), // 30 days
+        // prizes unclaimed 90 days after the draw go to a good-causes fund
+        claimDeadline: new anchor.BN(60 * 60 * 24 * 90), // 90 days
+        unclaimedPrizesBeneficiary: anchor.web3.Keypair.generate().publicKey,
+        ...schedule(20),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
       };
 
       const tx = await program.methods
@@ -194,10 +202,16 @@ describe("lottery_platform", () => {
         .accounts({
           owner: lotteryGameOwner.publicKey,
           lotteryGameConfigs: lotteryGameConfigs,
+          lotteryOperator: lotteryOperator,
+          lotteryGameCounter: lotteryGameCounter,
           lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
           treasuryVault: treasuryVault,
+          mintToken: mintToken.publicKey,
+          vaultTokens: vaultTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([lotteryGameOwner, depositAccount])
@@ -223,32 +237,72 @@ describe("lottery_platform", () => {
     }
   });
 
-  it("Is create token!", async () => {
-    console.log("mint token: ", mintToken.publicKey.toBase58());
-    console.log("token account: ", tokenAccount.toBase58());
-
-    try {
-      let initParams = {
-        amount: new anchor.BN(200),
-      };
-
-      const tx = await program.methods
-        .createToken(initParams)
-        .accounts({
-          owner: payer.publicKey,
-          lotteryGame: lotteryGame,
-          mintToken: mintToken.publicKey,
-          tokenAccount: tokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .signers([mintToken])
-        .rpc();
-      console.log("Your transaction signature", tx);
-    } catch (error) {
-      console.log(error);
-    }
+  it("Is register second lottery game for the same admin!", async () => {
+    const weeklyDepositAccount = anchor.web3.Keypair.generate();
+    const weeklyPdaAuth = pdaAuthAddress(weeklyDepositAccount.publicKey);
+    const weeklyTreasuryVault = treasuryVaultAddress(weeklyPdaAuth);
+    const weeklyLotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 1);
+    const weeklyVaultTokens = vaultTokensAddress(weeklyLotteryGame);
+    const weeklyLotteryRound = lotteryRoundAddress(weeklyLotteryGame, 0);
+
+    let initParams = {
+      country: "KE",
+      lotteryGameName: "Lotto Ke Weekly",
+      lotteryWinningPercentage: 9000, // 90 % (basis points)
+      reservePercentage: 5000, // 50 % of the remaining 10 % (basis points)
+      operatorFeePercentage: null, // platform default
+      // single jackpot
+      prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+      operatorRevenueAccount: operatorRevenueATA,
+      reserveAccount: reserveATA,
+      unitCostOfLotteryTicket: new anchor.BN(1_000_000_000), // 1 token in base units (9 decimals)
+      paymentMode: { token: {} }, // tickets are paid in the token mint
+      valueDate: "05-10-2024",
+      drawMethod: { commitReveal: {} },
+      gameMode: { raffle: {} },
+      pickCount: 0,
+      numberRange: 0,
+      drawCommitment: Array.from(drawCommitment),
+      oracleAuthority: anchor.web3.PublicKey.default,
+      rolloverRule: { unwonAndUnclaimed: {} },
+      claimPeriod: new anchor.BN(60 * 60 * 24 * 30), // 30 days
+      claimDeadline: new anchor.BN(0), // no deadline
+      unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+      ...schedule(20),
+      minTickets: new anchor.BN(0), // no minimum
+      maxTicketsPerPurchase: new anchor.BN(0), // no limit
+      maxTicketsPerGame: new anchor.BN(0), // no limit
+    };
+
+    await program.methods
+      .registerLotteryGame(initParams)
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: weeklyLotteryGame,
+        lotteryRound: weeklyLotteryRound,
+        depositAccount: weeklyDepositAccount.publicKey,
+        pdaAuth: weeklyPdaAuth,
+        treasuryVault: weeklyTreasuryVault,
+        mintToken: mintToken.publicKey,
+        vaultTokens: weeklyVaultTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, weeklyDepositAccount])
+      .rpc();
+
+    // game ids are handed out in registration order
+    const weeklyGame = await program.account.lotteryGame.fetch(
+      weeklyLotteryGame
+    );
+    assert.equal(weeklyGame.gameId.toNumber(), 1);
+    const counter = await program.account.lotteryGameCounter.fetch(
+      lotteryGameCounter
+    );
+    assert.equal(counter.nextGameId.toNumber(), 2);
   });
 
   it("Is token transfer - first participant", async () => {
@@ -271,7 +325,7 @@ describe("lottery_platform", () => {
 
     try {
       let initParams = {
-        amount: new anchor.BN(70),
+        amount: new anchor.BN(70_000_000_000),
       };
       const tx = await program.methods
         .transferToken(initParams)
@@ -314,7 +368,7 @@ describe("lottery_platform", () => {
 
     try {
       let initParams = {
-        amount: new anchor.BN(100),
+        amount: new anchor.BN(100_000_000_000),
       };
       const tx = await program.methods
         .transferToken(initParams)
@@ -396,40 +450,29 @@ describe("lottery_platform", () => {
   });
 
   it("Is buy lottery ticket - first participant!", async () => {
-    try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintToken.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
-    } catch (error) {
-      console.log(error);
-    }
-
     try {
       let initParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(1),
+        // 10 tokens in base units (9 decimals)
+        amount: new anchor.BN(10_000_000_000),
+        quantity: new anchor.BN(1),
+        numbers: [],
+        quickPick: false,
       };
 
       const tx = await program.methods
         .buyLotteryTicket(initParams)
-        .accounts({
-          owner: firstParticipantOwner.publicKey,
-          lotteryGame: lotteryGame,
-          participant: firstParticipant,
-          senderTokens: firstParticipantOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATA.address,
-          mintToken: mintToken.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
+        .accounts(
+          buyAccounts(
+            firstParticipantOwner.publicKey,
+            lotteryGame,
+            lotteryRound,
+            0,
+            {
+              senderTokens: firstParticipantOwnerATA.publicKey,
+              mintToken: mintToken.publicKey,
+            }
+          )
+        )
         .signers([firstParticipantOwner])
         .rpc();
       console.log("Your transaction signature", tx);
@@ -449,40 +492,29 @@ describe("lottery_platform", () => {
   });
 
   it("Is buy lottery ticket - second participant!", async () => {
-    try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintToken.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
-    } catch (error) {
-      console.log(error);
-    }
-
     try {
       let initParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(1),
+        // 10 tokens in base units (9 decimals)
+        amount: new anchor.BN(10_000_000_000),
+        quantity: new anchor.BN(1),
+        numbers: [],
+        quickPick: false,
       };
 
       const tx = await program.methods
         .buyLotteryTicket(initParams)
-        .accounts({
-          owner: secondParticipantOwner.publicKey,
-          lotteryGame: lotteryGame,
-          participant: secondParticipant,
-          senderTokens: secondParticipantOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATA.address,
-          mintToken: mintToken.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
+        .accounts(
+          buyAccounts(
+            secondParticipantOwner.publicKey,
+            lotteryGame,
+            lotteryRound,
+            1,
+            {
+              senderTokens: secondParticipantOwnerATA.publicKey,
+              mintToken: mintToken.publicKey,
+            }
+          )
+        )
         .signers([secondParticipantOwner])
         .rpc();
       console.log("Your transaction signature", tx);
@@ -502,13 +534,11 @@ describe("lottery_platform", () => {
   });
 
   it("Is get lottery game winner!", async () => {
-    try {
-      let operator = {
-        operator: "Company Lotto Ke",
-      };
+    // the round can't be drawn before its draw time
+    await waitForDraw(lotteryRound);
 
+    try {
       let initParams = {
-        operator: operator,
         country: "KE",
         lotteryGameName: "Lotto Ke 100",
         /*
@@ -524,7 +554,9 @@ describe("lottery_platform", () => {
         .accounts({
           owner: lotteryGameOwner.publicKey,
           lotteryGameConfigs: lotteryGameConfigs,
+          lotteryOperator: lotteryOperator,
           lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
           treasuryVault: treasuryVault,
@@ -553,20 +585,50 @@ describe("lottery_platform", () => {
     }
   });
 
+  it("Is reveal lottery game winner!", async () => {
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(round.status, { closed: {} });
+    assert.equal(round.winningTickets.length, 1);
+  });
+
   it("Is withdraw lottery game winnings - first participant!", async () => {
     try {
-      let initParams = {
-        // 2 amount of token to withdraw (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(2),
-      };
+      // prizes are only paid into the winner's associated token account
+      const winnerATA = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        firstParticipantOwner,
+        mintToken.publicKey,
+        firstParticipantOwner.publicKey
+      );
+
+      // winning is proven by presenting the drawn ticket, with two prize
+      // tiers and two tickets sold both tickets are drawn
       const tx = await program.methods
-        .withdrawLotteryGameWinnings(initParams)
+        .withdrawLotteryGameWinnings()
         .accounts({
           owner: firstParticipantOwner.publicKey,
           lotteryGame: lotteryGame,
-          participant: firstParticipant,
-          senderTokens: treasuryVaultATA.address,
-          recipientTokens: firstParticipantOwnerATA.publicKey,
+          lotteryRound: lotteryRound,
+          gameEntry: gameEntryAddress(lotteryGame, firstParticipant),
+          ticket: ticketAddress(lotteryGame, 0),
+          senderTokens: vaultTokens,
+          recipientTokens: winnerATA.address,
           mintToken: mintToken.publicKey,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
@@ -600,18 +662,26 @@ describe("lottery_platform", () => {
 
   it("Is withdraw lottery game winnings - second participant!", async () => {
     try {
-      let initParams = {
-        // 2 amount of token to withdraw (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(2),
-      };
+      // prizes are only paid into the winner's associated token account
+      const winnerATA = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        secondParticipantOwner,
+        mintToken.publicKey,
+        secondParticipantOwner.publicKey
+      );
+
+      // winning is proven by presenting the drawn ticket, with two prize
+      // tiers and two tickets sold both tickets are drawn
       const tx = await program.methods
-        .withdrawLotteryGameWinnings(initParams)
+        .withdrawLotteryGameWinnings()
         .accounts({
           owner: secondParticipantOwner.publicKey,
           lotteryGame: lotteryGame,
-          participant: secondParticipant,
-          senderTokens: treasuryVaultATA.address,
-          recipientTokens: secondParticipantOwnerATA.publicKey,
+          lotteryRound: lotteryRound,
+          gameEntry: gameEntryAddress(lotteryGame, secondParticipant),
+          ticket: ticketAddress(lotteryGame, 1),
+          senderTokens: vaultTokens,
+          recipientTokens: winnerATA.address,
           mintToken: mintToken.publicKey,
           depositAccount: depositAccount.publicKey,
           pdaAuth: pdaAuth,
@@ -642,4 +712,70 @@ describe("lottery_platform", () => {
       console.log(error);
     }
   });
+
+  it("Is distribute lottery round proceeds!", async () => {
+    for (const owner of [lotteryGameOwner, adminOwner]) {
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintToken.publicKey,
+        owner.publicKey
+      );
+    }
+
+    await program.methods
+      .distributeLotteryRoundProceeds()
+      .accounts({
+        signer: payer.publicKey,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        senderTokens: vaultTokens,
+        operatorRevenueTokens: operatorRevenueATA,
+        reserveTokens: reserveATA,
+        mintToken: mintToken.publicKey,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.isTrue(round.isProceedsDistributed);
+  });
+
+  it("Is start next round!", async () => {
+    const nextLotteryRound = lotteryRoundAddress(lotteryGame, 1);
+
+    // every round commits to a fresh draw seed
+    const nextDrawSeed = randomBytes(32);
+    let initParams = {
+      drawCommitment: Array.from(
+        createHash("sha256").update(nextDrawSeed).digest()
+      ),
+      ...schedule(20),
+    };
+
+    await program.methods
+      .startNextRound(initParams)
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+        previousRound: lotteryRound,
+        nextRound: nextLotteryRound,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.currentRound.toNumber(), 1);
+    const nextRound = await program.account.lotteryRound.fetch(
+      nextLotteryRound
+    );
+    assert.equal(nextRound.roundNumber.toNumber(), 1);
+    assert.deepEqual(nextRound.status, { open: {} });
+  });
 });
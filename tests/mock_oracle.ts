@@ -0,0 +1,331 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { MockOracle } from "../target/types/mock_oracle";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
+import {
+  schedule,
+  waitForDraw,
+  eventsOf,
+  grantRoles,
+  setupLotteryGameOwner,
+  airdrop,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  treasuryVaultAddress,
+  vaultTokensAddress,
+  pdaAuthAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("mock_oracle", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const oracleProgram = anchor.workspace.MockOracle as Program<MockOracle>;
+  const oracleAuthority = anchor.web3.Keypair.generate();
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const drawAuthority = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+
+  let [oracleConfigs] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("oracle-configs")],
+    oracleProgram.programId
+  );
+  let [oracleSigner] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("oracle-authority")],
+    oracleProgram.programId
+  );
+  // only the upgrade authority (the provider wallet) can initialize the oracle
+  const oracleProgramData = anchor.web3.PublicKey.findProgramAddressSync(
+    [oracleProgram.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )[0];
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+
+  // first lottery game of the admin, i.e game id 0
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+
+  // first round of the lottery game, i.e round number 0
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+
+  // vault of the lottery game holding the ticket proceeds
+  const vaultTokens = vaultTokensAddress(lotteryGame);
+
+  let mint: anchor.web3.PublicKey;
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      9
+    );
+  });
+
+  it("Is initialized oracle!", async () => {
+    const initOracle = (
+      owner: anchor.web3.Keypair,
+      authority: anchor.web3.PublicKey
+    ) =>
+      oracleProgram.methods
+        .init({ authority })
+        .accounts({
+          owner: owner.publicKey,
+          oracleConfigs: oracleConfigs,
+          oracleSigner: oracleSigner,
+          program: oracleProgram.programId,
+          programData: oracleProgramData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+    // anyone else calling init first can't take over the oracle
+    try {
+      await initOracle(participantOwner, participantOwner.publicKey);
+      assert.fail("init by a non-upgrade authority should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidUpgradeAuthority");
+    }
+
+    try {
+      await initOracle(payer, anchor.web3.PublicKey.default);
+      assert.fail("init without an oracle authority should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidOracleAuthority");
+    }
+
+    await initOracle(payer, oracleAuthority.publicKey);
+
+    const configs = await oracleProgram.account.oracleConfigs.fetch(
+      oracleConfigs
+    );
+    assert.ok(configs.authority.equals(oracleAuthority.publicKey));
+  });
+
+  it("Is register oracle lottery game!", async () => {
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    // oracle draws can be requested by a draw authority other than the lottery game admin
+    await grantRoles(drawAuthority.publicKey, [{ draw: {} }]);
+
+    let initParams = {
+      country: "KE",
+      lotteryGameName: "Lotto Ke Oracle",
+      lotteryWinningPercentage: 9000, // 90 % (basis points)
+      reservePercentage: 0,
+      operatorFeePercentage: null, // platform default
+      // single jackpot
+      prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+      operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+      reserveAccount: anchor.web3.Keypair.generate().publicKey,
+      unitCostOfLotteryTicket: new anchor.BN(1_000_000_000), // 1 token in base units (9 decimals)
+      paymentMode: { token: {} }, // tickets are paid in the token mint
+      valueDate: "28-09-2024",
+      drawMethod: { oracle: {} },
+      gameMode: { raffle: {} },
+      pickCount: 0,
+      numberRange: 0,
+      drawCommitment: new Array(32).fill(0),
+      oracleAuthority: oracleSigner,
+      rolloverRule: { none: {} },
+      claimPeriod: new anchor.BN(0),
+      claimDeadline: new anchor.BN(0), // no deadline
+      unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+      ...schedule(10),
+      minTickets: new anchor.BN(0), // no minimum
+      maxTicketsPerPurchase: new anchor.BN(0), // no limit
+      maxTicketsPerGame: new anchor.BN(0), // no limit
+    };
+
+    // prize tiers can't share out more than the whole prize pool
+    try {
+      await program.methods
+        .registerLotteryGame({
+          ...initParams,
+          prizeTiers: [
+            { winners: 1, matches: 0, prizePercentage: 8000 },
+            { winners: 5, matches: 0, prizePercentage: 4000 },
+          ],
+        })
+        .accounts({
+          owner: lotteryGameOwner.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryOperator: lotteryOperator,
+          lotteryGameCounter: lotteryGameCounter,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          mintToken: mint,
+          vaultTokens: vaultTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([lotteryGameOwner, depositAccount])
+        .rpc();
+      assert.fail("prize tiers over 100% should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidPercentage");
+    }
+
+    await program.methods
+      .registerLotteryGame(initParams)
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: mint,
+        vaultTokens: vaultTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+  });
+
+  it("Is buy lottery ticket!", async () => {
+    const participantATA = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      participantOwner.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      participantATA.address,
+      payer,
+      10_000_000_000
+    );
+
+    await program.methods
+      .registerParticipant({ fullNames: "mary jane", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    await buyTicket(
+      participantOwner,
+      buyAccounts(participantOwner.publicKey, lotteryGame, lotteryRound, 0, {
+        senderTokens: participantATA.address,
+        mintToken: mint,
+      }),
+      1_000_000_000
+    ).rpc();
+  });
+
+  it("Is request and fulfil lottery game randomness!", async () => {
+    // the round can't be drawn before its draw time
+    await waitForDraw(lotteryRound);
+
+    const requested = await program.methods
+      .requestLotteryGameRandomness()
+      .accounts({
+        drawAuthority: drawAuthority.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+      })
+      .signers([drawAuthority])
+      .rpc();
+
+    let result = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(result.status, { drawPending: {} });
+    assert.equal(result.randomnessRequestId.toNumber(), 1);
+
+    const [event] = await eventsOf(requested);
+    assert.equal(event.name, "drawRequested");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.deepEqual(event.data.drawMethod, { oracle: {} });
+    assert.equal(event.data.drawSlot.toNumber(), result.drawSlot.toNumber());
+    assert.equal(event.data.randomnessRequestId.toNumber(), 1);
+
+    // fulfilment signed by anyone but the configured oracle authority is rejected
+    try {
+      await program.methods
+        .fulfilLotteryGameRandomness({
+          requestId: new anchor.BN(1),
+          randomness: new Array(32).fill(7),
+        })
+        .accounts({
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          oracleAuthority: lotteryGameOwner.publicKey,
+        })
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("fulfilment by a non-oracle signer should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidOracleAuthority");
+    }
+
+    const fulfilRandomness = (authority: anchor.web3.Keypair) =>
+      oracleProgram.methods
+        .fulfilRandomness()
+        .accounts({
+          oracleConfigs: oracleConfigs,
+          oracleSigner: oracleSigner,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          authority: authority.publicKey,
+          lotteryPlatformProgram: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+    // the oracle only fulfils requests signed by its own authority
+    try {
+      await fulfilRandomness(participantOwner);
+      assert.fail("fulfilment by another signer than the oracle's should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidOracleAuthority");
+    }
+
+    await fulfilRandomness(oracleAuthority);
+
+    result = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(result.status, { closed: {} });
+    assert.equal(result.winningTickets.length, 1);
+    assert.equal(result.winningTickets[0].ticketNumber.toNumber(), 0);
+  });
+});
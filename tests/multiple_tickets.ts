@@ -0,0 +1,293 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitForDraw,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("multiple_tickets", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const firstParticipantOwner = anchor.web3.Keypair.generate();
+  const secondParticipantOwner = anchor.web3.Keypair.generate();
+
+  const ticketPrice = anchor.web3.LAMPORTS_PER_SOL / 10; // 0.1 sol in lamports
+  const maxTicketsPerPurchase = 5;
+  const maxTicketsPerGame = 8;
+
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  // a ticket is addressed by the ticket number of its first entry
+  const buyTickets = (
+    owner: anchor.web3.Keypair,
+    ticketNumber: number,
+    quantity: number,
+    amount: number
+  ) =>
+    buyTicket(
+      owner,
+      buyAccounts(owner.publicKey, lotteryGame, lotteryRound, ticketNumber, {
+        treasuryVault,
+      }),
+      amount,
+      quantity
+    ).rpc();
+
+  let rentExemptMinimum: number;
+
+  before(async () => {
+    await airdrop([
+      lotteryGameOwner,
+      firstParticipantOwner,
+      secondParticipantOwner,
+    ]);
+
+    rentExemptMinimum =
+      await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Multi",
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(ticketPrice),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(15),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(maxTicketsPerPurchase),
+        maxTicketsPerGame: new anchor.BN(maxTicketsPerGame),
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    for (const owner of [firstParticipantOwner, secondParticipantOwner]) {
+      await program.methods
+        .registerParticipant({ fullNames: "john doe", country: "KE" })
+        .accounts({
+          owner: owner.publicKey,
+          participant: participantAddress(owner.publicKey),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    }
+  });
+
+  it("Is buy three tickets in one purchase!", async () => {
+    await buyTickets(firstParticipantOwner, 0, 3, 3 * ticketPrice);
+
+    // the three tickets are paid in a single transfer
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(vaultBalance, rentExemptMinimum + 3 * ticketPrice);
+
+    const ticket = await program.account.ticket.fetch(
+      ticketAddress(lotteryGame, 0)
+    );
+    assert.equal(ticket.ticketNumber.toNumber(), 0);
+    assert.equal(ticket.quantity.toNumber(), 3);
+    assert.equal(ticket.amountPaid.toNumber(), 3 * ticketPrice);
+
+    // each ticket is an entry in the draw
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.equal(round.ticketsSold.toNumber(), 3);
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.ticketsSold.toNumber(), 3);
+  });
+
+  it("Is reject purchases not paying the price of every ticket!", async () => {
+    try {
+      await buyTickets(secondParticipantOwner, 3, 3, 2 * ticketPrice);
+      assert.fail("paying for fewer tickets than bought should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidLotteryTicketAmount");
+    }
+
+    try {
+      await buyTickets(secondParticipantOwner, 3, 0, ticketPrice);
+      assert.fail("buying no tickets should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidTicketQuantity");
+    }
+  });
+
+  it("Is enforce the ticket limits!", async () => {
+    try {
+      await buyTickets(
+        secondParticipantOwner,
+        3,
+        maxTicketsPerPurchase + 1,
+        (maxTicketsPerPurchase + 1) * ticketPrice
+      );
+      assert.fail("buying more tickets than a purchase allows should fail");
+    } catch (error) {
+      assert.include(error.toString(), "TicketLimitExceeded");
+    }
+
+    // fills the lottery game up to its limit
+    await buyTickets(secondParticipantOwner, 3, 5, 5 * ticketPrice);
+
+    try {
+      await buyTickets(secondParticipantOwner, 8, 1, ticketPrice);
+      assert.fail("buying more tickets than the game allows should fail");
+    } catch (error) {
+      assert.include(error.toString(), "TicketLimitExceeded");
+    }
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.ticketsSold.toNumber(), maxTicketsPerGame);
+  });
+
+  it("Is pay the jackpot to the ticket holding the winning entry!", async () => {
+    await waitForDraw(lotteryRound);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Multi",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // entries 0-2 belong to the first ticket, entries 3-7 to the second
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    const winningEntry = round.winningTickets[0].ticketNumber.toNumber();
+    const [winner, winningTicket] =
+      winningEntry < 3
+        ? [firstParticipantOwner, ticketAddress(lotteryGame, 0)]
+        : [secondParticipantOwner, ticketAddress(lotteryGame, 3)];
+
+    const withdraw = () =>
+      program.methods
+        .withdrawLotteryGameWinnings()
+        .accounts({
+          owner: winner.publicKey,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          gameEntry: gameEntryAddress(
+            lotteryGame,
+            participantAddress(winner.publicKey)
+          ),
+          ticket: winningTicket,
+          senderTokens: null,
+          recipientTokens: null,
+          mintToken: null,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: null,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([winner])
+        .rpc();
+
+    await withdraw();
+
+    // the whole pool is the jackpot
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(vaultBalance, rentExemptMinimum);
+
+    const ticket = await program.account.ticket.fetch(winningTicket);
+    assert.isTrue(ticket.isPrizeClaimed);
+
+    try {
+      await withdraw();
+      assert.fail("claiming the jackpot twice should fail");
+    } catch (error) {
+      assert.include(error.toString(), "PrizeAlreadyClaimed");
+    }
+  });
+});
@@ -0,0 +1,219 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  eventsOf,
+  initConfigs,
+  grantRoles,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  pdaAuthAddress,
+  treasuryVaultAddress,
+  airdrop,
+} from "./helpers";
+
+describe("operators", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+
+  // next lottery game of the admin, with its own deposit account
+  const newGame = (gameId: number) => {
+    const depositAccount = anchor.web3.Keypair.generate();
+    const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+    const treasuryVault = treasuryVaultAddress(pdaAuth);
+    const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, gameId);
+    const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+
+    return {
+      depositAccount,
+      pdaAuth,
+      treasuryVault,
+      lotteryGame,
+      lotteryRound,
+    };
+  };
+
+  const registerGame = (game: ReturnType<typeof newGame>, country: string) => {
+    const now = Math.floor(Date.now() / 1000);
+    return program.methods
+      .registerLotteryGame({
+        country: country,
+        lotteryGameName: "Lotto Ke",
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(1_000_000),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(
+          createHash("sha256").update(randomBytes(32)).digest()
+        ),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        salesOpenAt: new anchor.BN(now - 60),
+        salesCloseAt: new anchor.BN(now + 600),
+        drawAt: new anchor.BN(now + 600),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: game.lotteryGame,
+        lotteryRound: game.lotteryRound,
+        depositAccount: game.depositAccount.publicKey,
+        pdaAuth: game.pdaAuth,
+        treasuryVault: game.treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, game.depositAccount]);
+  };
+
+  const updateOperatorStatus = (
+    status: object,
+    owner: anchor.web3.PublicKey = payer.publicKey
+  ) =>
+    program.methods.updateOperatorStatus({ status: status }).accounts({
+      owner: owner,
+      lotteryGameConfigs: lotteryGameConfigs,
+      lotteryOperator: lotteryOperator,
+    });
+
+  const registerOperator = () =>
+    program.methods
+      .registerOperator({
+        authority: lotteryGameOwner.publicKey,
+        name: "Company Lotto Ke",
+        licensedCountries: ["KE", "TZ"],
+      })
+      .accounts({
+        owner: payer.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      });
+
+  before(async () => {
+    await airdrop([lotteryGameOwner], anchor.web3.LAMPORTS_PER_SOL);
+
+    await initConfigs();
+
+    await grantRoles(lotteryGameOwner.publicKey);
+  });
+
+  it("Is register lottery operator!", async () => {
+    const signature = await registerOperator().rpc();
+
+    const operator = await program.account.lotteryOperator.fetch(
+      lotteryOperator
+    );
+    assert.ok(operator.authority.equals(lotteryGameOwner.publicKey));
+    assert.equal(operator.name, "Company Lotto Ke");
+    assert.deepEqual(operator.licensedCountries, ["KE", "TZ"]);
+    assert.deepEqual(operator.status, { active: {} });
+    assert.equal(operator.gameCount.toNumber(), 0);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "operatorRegistered");
+    assert.ok(event.data.lotteryOperator.equals(lotteryOperator));
+    assert.ok(event.data.authority.equals(lotteryGameOwner.publicKey));
+    assert.deepEqual(event.data.licensedCountries, ["KE", "TZ"]);
+
+    try {
+      await registerOperator().rpc();
+      assert.fail("an operator is only registered once");
+    } catch (error) {
+      assert.include(error.toString(), "already in use");
+    }
+  });
+
+  it("Is register lottery games in licensed countries only!", async () => {
+    try {
+      await registerGame(newGame(0), "UG").rpc();
+      assert.fail("the operator isn't licensed in Uganda");
+    } catch (error) {
+      assert.include(error.toString(), "CountryNotLicensed");
+    }
+
+    const game = newGame(0);
+    await registerGame(game, "KE").rpc();
+
+    const lotteryGame = await program.account.lotteryGame.fetch(
+      game.lotteryGame
+    );
+    assert.ok(lotteryGame.operator.equals(lotteryOperator));
+
+    const operator = await program.account.lotteryOperator.fetch(
+      lotteryOperator
+    );
+    assert.equal(operator.gameCount.toNumber(), 1);
+  });
+
+  it("Is suspend and reinstate lottery operator!", async () => {
+    try {
+      await updateOperatorStatus({ suspended: {} }, lotteryGameOwner.publicKey)
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("only the super admin can suspend operators");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSuperAdmin");
+    }
+
+    const signature = await updateOperatorStatus({ suspended: {} }).rpc();
+
+    let operator = await program.account.lotteryOperator.fetch(
+      lotteryOperator
+    );
+    assert.deepEqual(operator.status, { suspended: {} });
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "operatorStatusUpdated");
+    assert.deepEqual(event.data.status, { suspended: {} });
+    assert.ok(event.data.superAdmin.equals(payer.publicKey));
+
+    try {
+      await registerGame(newGame(1), "TZ").rpc();
+      assert.fail("a suspended operator can't register lottery games");
+    } catch (error) {
+      assert.include(error.toString(), "OperatorSuspended");
+    }
+
+    await updateOperatorStatus({ active: {} }).rpc();
+    await registerGame(newGame(1), "TZ").rpc();
+
+    operator = await program.account.lotteryOperator.fetch(lotteryOperator);
+    assert.deepEqual(operator.status, { active: {} });
+    assert.equal(operator.gameCount.toNumber(), 2);
+  });
+});
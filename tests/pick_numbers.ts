@@ -0,0 +1,722 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import {
+  createAccount,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitForDraw,
+  eventsOf,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  vaultTokensAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("pick_numbers", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+
+  // secret draw seed, only its sha256 hash is committed at registration
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+
+  // first lottery game of the admin, i.e game id 0
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+
+  // first round of the lottery game, i.e round number 0
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+
+  // vault of the lottery game holding the ticket proceeds
+  const vaultTokens = vaultTokensAddress(lotteryGame);
+
+  let mint: anchor.web3.PublicKey;
+  let participantATA: anchor.web3.PublicKey;
+
+  const purchaseAccounts = (ticketNumber: number, round = lotteryRound) =>
+    buyAccounts(participantOwner.publicKey, lotteryGame, round, ticketNumber, {
+      senderTokens: participantATA,
+      mintToken: mint,
+    });
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      9
+    );
+  });
+
+  it("Is register pick numbers lottery game!", async () => {
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    // pick 3 numbers out of 10, matching 3 wins the jackpot and matching 2 the rest
+    let initParams = {
+      country: "KE",
+      lotteryGameName: "Lotto Ke 3/10",
+      lotteryWinningPercentage: 9000, // 90 % (basis points)
+      reservePercentage: 0,
+      operatorFeePercentage: null, // platform default
+      prizeTiers: [
+        { winners: 0, matches: 3, prizePercentage: 7000 },
+        { winners: 0, matches: 2, prizePercentage: 3000 },
+      ],
+      operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+      reserveAccount: anchor.web3.Keypair.generate().publicKey,
+      unitCostOfLotteryTicket: new anchor.BN(10_000_000_000), // 10 tokens in base units (9 decimals)
+      paymentMode: { token: {} }, // tickets are paid in the token mint
+      valueDate: "28-09-2024",
+      drawMethod: { commitReveal: {} },
+      gameMode: { pickNumbers: {} },
+      pickCount: 3,
+      numberRange: 10,
+      drawCommitment: Array.from(drawCommitment),
+      oracleAuthority: anchor.web3.PublicKey.default,
+      rolloverRule: { none: {} },
+      claimPeriod: new anchor.BN(0),
+      claimDeadline: new anchor.BN(0), // no deadline
+      unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+      ...schedule(15),
+      minTickets: new anchor.BN(0), // no minimum
+      maxTicketsPerPurchase: new anchor.BN(0), // no limit
+      maxTicketsPerGame: new anchor.BN(0), // no limit
+    };
+
+    await program.methods
+      .registerLotteryGame(initParams)
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: mint,
+        vaultTokens: vaultTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    let result = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.deepEqual(result.gameMode, { pickNumbers: {} });
+    assert.equal(result.pickCount, 3);
+    assert.equal(result.numberRange, 10);
+  });
+
+  it("Is buy lottery tickets with picked and quick picked numbers!", async () => {
+    participantATA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        participantOwner.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      participantATA,
+      payer,
+      100_000_000_000
+    );
+
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    // numbers must be distinct and within 1 to 10
+    for (const numbers of [
+      [1, 1, 2],
+      [0, 1, 2],
+      [1, 2, 11],
+      [1, 2],
+    ]) {
+      try {
+        await buyTicket(
+          participantOwner,
+          purchaseAccounts(0),
+          10_000_000_000,
+          1,
+          numbers
+        ).rpc();
+        assert.fail("invalid numbers should be rejected");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidLotteryNumbers");
+      }
+    }
+
+    await buyTicket(
+      participantOwner,
+      purchaseAccounts(0),
+      10_000_000_000,
+      1,
+      [7, 1, 3]
+    ).rpc();
+
+    let ticket = await program.account.ticket.fetch(
+      ticketAddress(lotteryGame, 0)
+    );
+    assert.deepEqual(Array.from(ticket.numbers), [1, 3, 7]);
+
+    await buyTicket(
+      participantOwner,
+      purchaseAccounts(1),
+      10_000_000_000,
+      1,
+      [],
+      true
+    ).rpc();
+
+    ticket = await program.account.ticket.fetch(ticketAddress(lotteryGame, 1));
+    const numbers = Array.from(ticket.numbers);
+    assert.equal(numbers.length, 3);
+    assert.equal(new Set(numbers).size, 3);
+    numbers.forEach((number) => assert.isTrue(number >= 1 && number <= 10));
+  });
+
+  it("Is draw and tally winning numbers!", async () => {
+    // the round can't be drawn before its draw time
+    await waitForDraw(lotteryRound);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke 3/10",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    let round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(round.status, { tallying: {} });
+    const winningNumbers = Array.from(round.winningNumbers);
+    assert.equal(winningNumbers.length, 3);
+
+    // the draw has already split the pool, the round can't be unwound any more
+    try {
+      await program.methods
+        .cancelLotteryGame()
+        .accounts({
+          owner: lotteryGameOwner.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+        })
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("a round being tallied can't be cancelled");
+    } catch (error) {
+      assert.include(error.toString(), "LotteryRoundAlreadyDrawn");
+    }
+
+    // tickets have to be tallied in ticket number order
+    try {
+      await program.methods
+        .tallyLotteryRoundTickets()
+        .accounts({
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          signer: payer.publicKey,
+        })
+        .remainingAccounts([
+          {
+            pubkey: ticketAddress(lotteryGame, 1),
+            isWritable: false,
+            isSigner: false,
+          },
+        ])
+        .rpc();
+      assert.fail("out of order tally should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidTallyOrder");
+    }
+
+    await program.methods
+      .tallyLotteryRoundTickets()
+      .accounts({
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        signer: payer.publicKey,
+      })
+      .remainingAccounts(
+        [0, 1].map((ticketNumber) => ({
+          pubkey: ticketAddress(lotteryGame, ticketNumber),
+          isWritable: false,
+          isSigner: false,
+        }))
+      )
+      .rpc();
+
+    round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(round.status, { closed: {} });
+    assert.equal(round.talliedTickets.toNumber(), 2);
+
+    // each tier counts the tickets matching exactly its number of matches
+    let expectedWinners = [0, 0];
+    for (const ticketNumber of [0, 1]) {
+      const ticket = await program.account.ticket.fetch(
+        ticketAddress(lotteryGame, ticketNumber)
+      );
+      const matches = Array.from(ticket.numbers).filter((number) =>
+        winningNumbers.includes(number)
+      ).length;
+      if (matches == 3) expectedWinners[0] += 1;
+      if (matches == 2) expectedWinners[1] += 1;
+    }
+    assert.deepEqual(
+      round.tierResults.map((tierResult) => tierResult.winners.toNumber()),
+      expectedWinners
+    );
+  });
+
+  it("Is withdraw pick numbers winnings!", async () => {
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    const winningNumbers = Array.from(round.winningNumbers);
+
+    // prizes are only paid into the winner's associated token account, neither
+    // another token account of the winner nor someone else's account is accepted
+    const otherAccount = await createAccount(
+      provider.connection,
+      payer,
+      mint,
+      participantOwner.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    const payerATA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey
+      )
+    ).address;
+
+    for (const ticketNumber of [0, 1]) {
+      const ticket = await program.account.ticket.fetch(
+        ticketAddress(lotteryGame, ticketNumber)
+      );
+      const matches = Array.from(ticket.numbers).filter((number) =>
+        winningNumbers.includes(number)
+      ).length;
+      const tier = [3, 2].indexOf(matches);
+
+      const withdraw = (recipientTokens = participantATA) =>
+        program.methods
+          .withdrawLotteryGameWinnings()
+          .accounts({
+            owner: participantOwner.publicKey,
+            lotteryGame: lotteryGame,
+            lotteryRound: lotteryRound,
+            gameEntry: gameEntryAddress(lotteryGame, participant),
+            ticket: ticketAddress(lotteryGame, ticketNumber),
+            senderTokens: vaultTokens,
+            recipientTokens: recipientTokens,
+            mintToken: mint,
+            depositAccount: depositAccount.publicKey,
+            pdaAuth: pdaAuth,
+            treasuryVault: treasuryVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associateTokenProgram: associateTokenProgram,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([participantOwner])
+          .rpc();
+
+      for (const recipientTokens of [otherAccount, payerATA]) {
+        try {
+          await withdraw(recipientTokens);
+          assert.fail(
+            "prizes should only go to the winner's associated account"
+          );
+        } catch (error) {
+          assert.include(error.toString(), "InvalidWinnerTokenAccount");
+        }
+      }
+
+      if (tier < 0) {
+        // tickets matching no tier have nothing to claim
+        try {
+          await withdraw();
+          assert.fail("a losing ticket should not be paid");
+        } catch (error) {
+          assert.include(error.toString(), "InvalidLotteryGameWinner");
+        }
+        continue;
+      }
+
+      await withdraw();
+
+      const claimed = await program.account.ticket.fetch(
+        ticketAddress(lotteryGame, ticketNumber)
+      );
+      assert.isTrue(claimed.isPrizeClaimed);
+
+      // a ticket's prize can only be claimed once
+      try {
+        await withdraw();
+        assert.fail("a second claim should be rejected");
+      } catch (error) {
+        assert.include(error.toString(), "PrizeAlreadyClaimed");
+      }
+    }
+  });
+
+  it("Is refund every ticket of a cancelled pick numbers round!", async () => {
+    // second round of the lottery game, i.e round number 1
+    const nextRound = lotteryRoundAddress(lotteryGame, 1);
+
+    const nextSchedule = schedule(600);
+    const signature = await program.methods
+      .startNextRound({
+        drawCommitment: Array.from(
+          createHash("sha256").update(randomBytes(32)).digest()
+        ),
+        ...nextSchedule,
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+        previousRound: lotteryRound,
+        nextRound: nextRound,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "roundStarted");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 1);
+    assert.deepEqual(event.data.previousRoundStatus, { closed: {} });
+    assert.equal(
+      event.data.salesCloseAt.toNumber(),
+      nextSchedule.salesCloseAt.toNumber()
+    );
+    assert.equal(event.data.drawAt.toNumber(), nextSchedule.drawAt.toNumber());
+
+    // tickets 2 and 3, the second one with two entries
+    for (const [ticketNumber, quantity] of [
+      [2, 1],
+      [3, 2],
+    ]) {
+      await buyTicket(
+        participantOwner,
+        purchaseAccounts(ticketNumber, nextRound),
+        quantity * 10_000_000_000,
+        quantity,
+        [],
+        true
+      ).rpc();
+    }
+
+    await program.methods
+      .cancelLotteryGame()
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+        lotteryRound: nextRound,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const balance = async () =>
+      Number(
+        (await provider.connection.getTokenAccountBalance(participantATA)).value
+          .amount
+      );
+    const balanceBefore = await balance();
+
+    for (const ticketNumber of [2, 3]) {
+      await program.methods
+        .claimRefund()
+        .accounts({
+          owner: participantOwner.publicKey,
+          lotteryGame: lotteryGame,
+          lotteryRound: nextRound,
+          participant: participant,
+          gameEntry: gameEntryAddress(lotteryGame, participant),
+          ticket: ticketAddress(lotteryGame, ticketNumber),
+          senderTokens: vaultTokens,
+          recipientTokens: participantATA,
+          mintToken: mint,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([participantOwner])
+        .rpc();
+    }
+
+    // every entry is paid back in full and nothing is left in the round
+    assert.equal((await balance()) - balanceBefore, 30_000_000_000);
+    const round = await program.account.lotteryRound.fetch(nextRound);
+    assert.deepEqual(round.status, { refundable: {} });
+    assert.equal(round.amountsRefunded.toNumber(), 30_000_000_000);
+    assert.equal(round.availableFunds.toNumber(), 0);
+  });
+
+  it("Is roll over the prize shares of tiers nobody matched!", async () => {
+    // second lottery game of the admin, i.e game id 1, a 6/49 game paid in sol
+    const unwonDepositAccount = anchor.web3.Keypair.generate();
+    const unwonPdaAuth = pdaAuthAddress(unwonDepositAccount.publicKey);
+    const unwonTreasuryVault = treasuryVaultAddress(unwonPdaAuth);
+    const unwonLotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 1);
+    const firstRound = lotteryRoundAddress(unwonLotteryGame, 0);
+    const secondRound = lotteryRoundAddress(unwonLotteryGame, 1);
+    const unwonDrawSeed = randomBytes(32);
+    const prizeTiers = [
+      { winners: 0, matches: 6, prizePercentage: 5000 },
+      { winners: 0, matches: 5, prizePercentage: 3000 },
+      { winners: 0, matches: 4, prizePercentage: 2000 },
+    ];
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke 6/49",
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        prizeTiers: prizeTiers,
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(
+          anchor.web3.LAMPORTS_PER_SOL / 10
+        ),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { pickNumbers: {} },
+        pickCount: 6,
+        numberRange: 49,
+        drawCommitment: Array.from(
+          createHash("sha256").update(unwonDrawSeed).digest()
+        ),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { unwon: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: unwonLotteryGame,
+        lotteryRound: firstRound,
+        depositAccount: unwonDepositAccount.publicKey,
+        pdaAuth: unwonPdaAuth,
+        treasuryVault: unwonTreasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, unwonDepositAccount])
+      .rpc();
+
+    // a single ticket, it hits the jackpot once in 13,983,816 draws
+    await buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        unwonLotteryGame,
+        firstRound,
+        0,
+        { treasuryVault: unwonTreasuryVault }
+      ),
+      anchor.web3.LAMPORTS_PER_SOL / 10,
+      1,
+      [1, 2, 3, 4, 5, 6]
+    ).rpc();
+
+    await waitForDraw(firstRound);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke 6/49",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: unwonLotteryGame,
+        lotteryRound: firstRound,
+        depositAccount: unwonDepositAccount.publicKey,
+        pdaAuth: unwonPdaAuth,
+        treasuryVault: unwonTreasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(unwonDrawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: unwonLotteryGame,
+        lotteryRound: firstRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    await program.methods
+      .tallyLotteryRoundTickets()
+      .accounts({
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: unwonLotteryGame,
+        lotteryRound: firstRound,
+        signer: payer.publicKey,
+      })
+      .remainingAccounts([
+        {
+          pubkey: ticketAddress(unwonLotteryGame, 0),
+          isWritable: false,
+          isSigner: false,
+        },
+      ])
+      .rpc();
+
+    // nobody won the jackpot, its share and those of empty tiers are unwon
+    const round = await program.account.lotteryRound.fetch(firstRound);
+    assert.deepEqual(round.status, { closed: {} });
+    assert.equal(round.tierResults[0].winners.toNumber(), 0);
+    const prizeAmount = round.prizeAmount.toNumber();
+    const unwonAmount = round.tierResults
+      .map((tierResult, tier) =>
+        tierResult.winners.toNumber() == 0
+          ? Math.floor((prizeAmount * prizeTiers[tier].prizePercentage) / 10000)
+          : 0
+      )
+      .reduce((sum, share) => sum + share, 0);
+    assert.equal(round.unwonAmount.toNumber(), unwonAmount);
+
+    const signature = await program.methods
+      .startNextRound({
+        drawCommitment: Array.from(
+          createHash("sha256").update(randomBytes(32)).digest()
+        ),
+        ...schedule(600),
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: unwonLotteryGame,
+        previousRound: firstRound,
+        nextRound: secondRound,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the unwon shares seed the next jackpot, won prizes stay claimable
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "roundStarted");
+    assert.equal(event.data.rolloverAmount.toNumber(), unwonAmount);
+
+    const next = await program.account.lotteryRound.fetch(secondRound);
+    assert.equal(next.rolloverAmount.toNumber(), unwonAmount);
+    assert.equal(next.availableFunds.toNumber(), unwonAmount);
+
+    const previous = await program.account.lotteryRound.fetch(firstRound);
+    assert.equal(previous.rolledOverAmount.toNumber(), unwonAmount);
+    assert.equal(
+      previous.availableFunds.toNumber(),
+      round.availableFunds.toNumber() - unwonAmount
+    );
+  });
+});
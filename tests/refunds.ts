@@ -0,0 +1,666 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitForDraw,
+  eventsOf,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  vaultTokensAddress,
+  buyAccounts,
+  buyTicket,
+  waitUntil,
+} from "./helpers";
+
+describe("refunds", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+
+  // the draw seed is never revealed, the game is cancelled before its draw
+  const drawCommitment = createHash("sha256")
+    .update(randomBytes(32))
+    .digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+
+  // first lottery game of the admin, i.e game id 0
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+
+  // first round of the lottery game, i.e round number 0
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+
+  // vault of the lottery game holding the ticket proceeds
+  const vaultTokens = vaultTokensAddress(lotteryGame);
+
+  let mint: anchor.web3.PublicKey;
+  let participantATA: anchor.web3.PublicKey;
+
+  const buy = (ticketNumber: number) =>
+    buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        lotteryGame,
+        lotteryRound,
+        ticketNumber,
+        { senderTokens: participantATA, mintToken: mint }
+      ),
+      10_000_000_000
+    );
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      9
+    );
+  });
+
+  const refundAccounts = (ticketNumber: number) => ({
+    owner: participantOwner.publicKey,
+    lotteryGame: lotteryGame,
+    lotteryRound: lotteryRound,
+    participant: participant,
+    gameEntry: gameEntryAddress(lotteryGame, participant),
+    ticket: ticketAddress(lotteryGame, ticketNumber),
+    senderTokens: vaultTokens,
+    recipientTokens: participantATA,
+    mintToken: mint,
+    depositAccount: depositAccount.publicKey,
+    pdaAuth: pdaAuth,
+    treasuryVault: treasuryVault,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    associateTokenProgram: associateTokenProgram,
+    systemProgram: anchor.web3.SystemProgram.programId,
+  });
+
+  it("Is register lottery game and buy lottery ticket!", async () => {
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    let initParams = {
+      country: "KE",
+      lotteryGameName: "Lotto Ke Refunds",
+      lotteryWinningPercentage: 9000, // 90 % (basis points)
+      reservePercentage: 0,
+      operatorFeePercentage: null, // platform default
+      // single jackpot
+      prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+      operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+      reserveAccount: anchor.web3.Keypair.generate().publicKey,
+      unitCostOfLotteryTicket: new anchor.BN(10_000_000_000), // 10 tokens in base units (9 decimals)
+      paymentMode: { token: {} }, // tickets are paid in the token mint
+      valueDate: "28-09-2024",
+      drawMethod: { commitReveal: {} },
+      gameMode: { raffle: {} },
+      pickCount: 0,
+      numberRange: 0,
+      drawCommitment: Array.from(drawCommitment),
+      oracleAuthority: anchor.web3.PublicKey.default,
+      rolloverRule: { none: {} },
+      claimPeriod: new anchor.BN(0),
+      claimDeadline: new anchor.BN(0), // no deadline
+      unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+      ...schedule(600),
+      minTickets: new anchor.BN(0), // no minimum
+      maxTicketsPerPurchase: new anchor.BN(0), // no limit
+      maxTicketsPerGame: new anchor.BN(0), // no limit
+    };
+
+    await program.methods
+      .registerLotteryGame(initParams)
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: mint,
+        vaultTokens: vaultTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    participantATA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        participantOwner.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      participantATA,
+      payer,
+      100_000_000_000
+    );
+
+    await program.methods
+      .registerParticipant({ fullNames: "jane doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    await buy(0).rpc();
+
+    const ticket = await program.account.ticket.fetch(
+      ticketAddress(lotteryGame, 0)
+    );
+    assert.equal(ticket.amountPaid.toNumber(), 10_000_000_000);
+  });
+
+  it("Is cancel lottery game!", async () => {
+    // refunds are only possible once the round can't be drawn any more
+    try {
+      await program.methods
+        .claimRefund()
+        .accounts(refundAccounts(0))
+        .signers([participantOwner])
+        .rpc();
+      assert.fail("refund of an open round should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "RoundNotRefundable");
+    }
+
+    // only the lottery game admin can cancel the game
+    try {
+      await program.methods
+        .cancelLotteryGame()
+        .accounts({
+          owner: participantOwner.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+        })
+        .signers([participantOwner])
+        .rpc();
+      assert.fail("cancellation by a non-admin should fail");
+    } catch (error) {
+      assert.include(error.toString(), "MissingOperatorRole");
+    }
+
+    const signature = await program.methods
+      .cancelLotteryGame()
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.isTrue(game.isCancelled);
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.deepEqual(round.status, { refundable: {} });
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "gameCancelled");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 0);
+    assert.deepEqual(event.data.roundStatus, { refundable: {} });
+
+    // no more tickets can be sold
+    try {
+      await buy(1).rpc();
+      assert.fail("ticket sales of a cancelled game should fail");
+    } catch (error) {
+      assert.include(error.toString(), "LotteryGameCancelled");
+    }
+  });
+
+  it("Is claim refund!", async () => {
+    const balance = async () =>
+      Number(
+        (await provider.connection.getTokenAccountBalance(participantATA)).value
+          .amount
+      );
+    const balanceBefore = await balance();
+
+    const signature = await program.methods
+      .claimRefund()
+      .accounts(refundAccounts(0))
+      .signers([participantOwner])
+      .rpc();
+
+    // exactly the ticket price is paid back, 10 tokens with 9 decimals
+    assert.equal((await balance()) - balanceBefore, 10_000_000_000);
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.equal(round.amountsRefunded.toNumber(), 10_000_000_000);
+    assert.equal(round.availableFunds.toNumber(), 0);
+    const gameEntry = await program.account.gameEntry.fetch(
+      gameEntryAddress(lotteryGame, participant)
+    );
+    assert.equal(gameEntry.amountRefunded.toNumber(), 10_000_000_000);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "refundClaimed");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.ticketNumber.toNumber(), 0);
+    assert.ok(event.data.owner.equals(participantOwner.publicKey));
+    assert.equal(event.data.amount.toNumber(), 10_000_000_000);
+
+    // a ticket can only be refunded once
+    try {
+      await program.methods
+        .claimRefund()
+        .accounts(refundAccounts(0))
+        .signers([participantOwner])
+        .rpc();
+      assert.fail("a second refund should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "TicketAlreadyRefunded");
+    }
+  });
+
+  it("Is refund a round below the minimum tickets!", async () => {
+    // second lottery game of the admin, i.e game id 1
+    const minTicketsDepositAccount = anchor.web3.Keypair.generate();
+    const minTicketsPdaAuth = pdaAuthAddress(
+      minTicketsDepositAccount.publicKey
+    );
+    const minTicketsTreasuryVault = treasuryVaultAddress(minTicketsPdaAuth);
+    const minTicketsLotteryGame = lotteryGameAddress(
+      lotteryGameOwner.publicKey,
+      1
+    );
+    const minTicketsLotteryRound = lotteryRoundAddress(
+      minTicketsLotteryGame,
+      0
+    );
+    const minTicketsVaultTokens = vaultTokensAddress(minTicketsLotteryGame);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Minimum",
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(10_000_000_000), // 10 tokens in base units (9 decimals)
+        paymentMode: { token: {} }, // tickets are paid in the token mint
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(2), // the round needs two tickets to be drawn
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: minTicketsLotteryGame,
+        lotteryRound: minTicketsLotteryRound,
+        depositAccount: minTicketsDepositAccount.publicKey,
+        pdaAuth: minTicketsPdaAuth,
+        treasuryVault: minTicketsTreasuryVault,
+        mintToken: mint,
+        vaultTokens: minTicketsVaultTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, minTicketsDepositAccount])
+      .rpc();
+
+    const buy = (ticketNumber: number) =>
+      buyTicket(
+        participantOwner,
+        buyAccounts(
+          participantOwner.publicKey,
+          minTicketsLotteryGame,
+          minTicketsLotteryRound,
+          ticketNumber,
+          { senderTokens: participantATA, mintToken: mint }
+        ),
+        10_000_000_000
+      ).rpc();
+    const claimRefund = () =>
+      program.methods
+        .claimRefund()
+        .accounts({
+          owner: participantOwner.publicKey,
+          lotteryGame: minTicketsLotteryGame,
+          lotteryRound: minTicketsLotteryRound,
+          participant: participant,
+          gameEntry: gameEntryAddress(minTicketsLotteryGame, participant),
+          ticket: ticketAddress(minTicketsLotteryGame, 0),
+          senderTokens: minTicketsVaultTokens,
+          recipientTokens: participantATA,
+          mintToken: mint,
+          depositAccount: minTicketsDepositAccount.publicKey,
+          pdaAuth: minTicketsPdaAuth,
+          treasuryVault: minTicketsTreasuryVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([participantOwner])
+        .rpc();
+
+    await buy(0);
+
+    // the round may still reach its minimum while sales are open
+    try {
+      await claimRefund();
+      assert.fail("refund before sales close should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "RoundNotRefundable");
+    }
+
+    await waitForDraw(minTicketsLotteryRound);
+
+    try {
+      await buy(1);
+      assert.fail("ticket sales after the sales window should fail");
+    } catch (error) {
+      assert.include(error.toString(), "SalesClosed");
+    }
+
+    // drawing a round below its minimum tickets makes it refundable instead
+    const signature = await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Minimum",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: minTicketsLotteryGame,
+        lotteryRound: minTicketsLotteryRound,
+        depositAccount: minTicketsDepositAccount.publicKey,
+        pdaAuth: minTicketsPdaAuth,
+        treasuryVault: minTicketsTreasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    let round = await program.account.lotteryRound.fetch(
+      minTicketsLotteryRound
+    );
+    assert.deepEqual(round.status, { refundable: {} });
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "roundRefundable");
+    assert.ok(event.data.lotteryGame.equals(minTicketsLotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 0);
+    assert.equal(event.data.ticketsSold.toNumber(), 1);
+    assert.equal(event.data.amountsRaised.toNumber(), 10_000_000_000);
+
+    await claimRefund();
+
+    round = await program.account.lotteryRound.fetch(minTicketsLotteryRound);
+    assert.equal(round.amountsRefunded.toNumber(), 10_000_000_000);
+  });
+
+  it("Is release the jackpot carried into a cancelled lottery game!", async () => {
+    // third lottery game of the admin, i.e game id 2, paid in sol
+    const jackpotDepositAccount = anchor.web3.Keypair.generate();
+    const jackpotPdaAuth = pdaAuthAddress(jackpotDepositAccount.publicKey);
+    const jackpotTreasuryVault = treasuryVaultAddress(jackpotPdaAuth);
+    const jackpotLotteryGame = lotteryGameAddress(
+      lotteryGameOwner.publicKey,
+      2
+    );
+    const firstRound = lotteryRoundAddress(jackpotLotteryGame, 0);
+    const secondRound = lotteryRoundAddress(jackpotLotteryGame, 1);
+    const reserveWallet = anchor.web3.Keypair.generate().publicKey;
+    const jackpotDrawSeed = randomBytes(32);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Jackpot",
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: reserveWallet,
+        unitCostOfLotteryTicket: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(
+          createHash("sha256").update(jackpotDrawSeed).digest()
+        ),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { unwonAndUnclaimed: {} },
+        claimPeriod: new anchor.BN(1), // unclaimed prizes roll over a second after the draw
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: jackpotLotteryGame,
+        lotteryRound: firstRound,
+        depositAccount: jackpotDepositAccount.publicKey,
+        pdaAuth: jackpotPdaAuth,
+        treasuryVault: jackpotTreasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, jackpotDepositAccount])
+      .rpc();
+
+    await buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        jackpotLotteryGame,
+        firstRound,
+        0,
+        { treasuryVault: jackpotTreasuryVault }
+      ),
+      anchor.web3.LAMPORTS_PER_SOL
+    ).rpc();
+
+    await waitForDraw(firstRound);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Jackpot",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: jackpotLotteryGame,
+        lotteryRound: firstRound,
+        depositAccount: jackpotDepositAccount.publicKey,
+        pdaAuth: jackpotPdaAuth,
+        treasuryVault: jackpotTreasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(jackpotDrawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: jackpotLotteryGame,
+        lotteryRound: firstRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const nextDrawSeed = randomBytes(32);
+    await program.methods
+      .startNextRound({
+        drawCommitment: Array.from(
+          createHash("sha256").update(nextDrawSeed).digest()
+        ),
+        ...schedule(60),
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: jackpotLotteryGame,
+        previousRound: firstRound,
+        nextRound: secondRound,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the unclaimed jackpot rolls into the second round once the claim period is over
+    const { drawnAt } = await program.account.lotteryRound.fetch(firstRound);
+    await waitUntil(drawnAt.toNumber() + 1);
+
+    await program.methods
+      .rolloverUnclaimedPrize()
+      .accounts({
+        lotteryGame: jackpotLotteryGame,
+        unclaimedRound: firstRound,
+        currentRound: secondRound,
+        signer: payer.publicKey,
+      })
+      .rpc();
+
+    const { rolloverAmount } = await program.account.lotteryRound.fetch(
+      secondRound
+    );
+    assert.isAbove(rolloverAmount.toNumber(), 0);
+
+    // without tickets, the cancelled second round ends unwon
+    await program.methods
+      .cancelLotteryGame()
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryGame: jackpotLotteryGame,
+        lotteryRound: secondRound,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const release = () =>
+      program.methods
+        .releaseCancelledJackpot()
+        .accounts({
+          lotteryGame: jackpotLotteryGame,
+          lotteryRound: secondRound,
+          senderTokens: null,
+          reserveTokens: null,
+          mintToken: null,
+          reserveWallet: reserveWallet,
+          depositAccount: jackpotDepositAccount.publicKey,
+          pdaAuth: jackpotPdaAuth,
+          treasuryVault: jackpotTreasuryVault,
+          signer: payer.publicKey,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    const signature = await release();
+
+    // the carried jackpot can't roll on, it goes to the reserve
+    const reserveBalance = await provider.connection.getBalance(reserveWallet);
+    assert.equal(reserveBalance, rolloverAmount.toNumber());
+
+    const round = await program.account.lotteryRound.fetch(secondRound);
+    assert.deepEqual(round.status, { unwon: {} });
+    assert.equal(round.availableFunds.toNumber(), 0);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "cancelledJackpotReleased");
+    assert.ok(event.data.lotteryGame.equals(jackpotLotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 1);
+    assert.ok(event.data.reserve.equals(reserveWallet));
+    assert.equal(event.data.amount.toNumber(), rolloverAmount.toNumber());
+
+    try {
+      await release();
+      assert.fail("releasing the jackpot twice should fail");
+    } catch (error) {
+      assert.include(error.toString(), "NothingToRelease");
+    }
+  });
+});
@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import {
+  eventsOf,
+  initConfigs,
+  registerLotteryOperator,
+  airdrop,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryRoundAddress,
+  pdaAuthAddress,
+  treasuryVaultAddress,
+  lotteryOperatorAddress,
+  programData,
+} from "./helpers";
+
+describe("roles", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const stranger = anchor.web3.Keypair.generate();
+  const newSuperAdmin = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+
+  const lotteryOperator = lotteryOperatorAddress(stranger.publicKey);
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryGameCounter = lotteryGameCounterAddress(stranger.publicKey);
+  const lotteryGame = lotteryGameAddress(stranger.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+
+  const grantRole = (
+    role: object,
+    authority: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey = payer.publicKey
+  ) =>
+    program.methods
+      .grantRole({ role: role, authority: authority })
+      .accounts({ owner: owner, lotteryGameConfigs: lotteryGameConfigs });
+
+  const revokeRole = (role: object, authority: anchor.web3.PublicKey) =>
+    program.methods
+      .revokeRole({ role: role, authority: authority })
+      .accounts({
+        owner: payer.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      });
+
+  const holdsRole = (authorities: anchor.web3.PublicKey[]) =>
+    authorities.some((authority) => authority.equals(stranger.publicKey));
+
+  const transferSuperAdmin = async (
+    from: anchor.web3.Keypair,
+    to: anchor.web3.Keypair
+  ) => {
+    await program.methods
+      .proposeSuperAdmin({ superAdmin: to.publicKey })
+      .accounts({
+        owner: from.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .signers([from])
+      .rpc();
+
+    await program.methods
+      .acceptSuperAdmin()
+      .accounts({
+        owner: to.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .signers([to])
+      .rpc();
+  };
+
+  before(async () => {
+    await airdrop([stranger, newSuperAdmin], anchor.web3.LAMPORTS_PER_SOL);
+
+    await initConfigs();
+
+    // a licensed operator, yet without the operator role
+    await registerLotteryOperator(stranger.publicKey);
+  });
+
+  it("Is reject init by anyone but the upgrade authority!", async () => {
+    let isInitialized = true;
+    try {
+      await program.methods
+        .init()
+        .accounts({
+          owner: stranger.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          program: program.programId,
+          programData: programData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+    } catch (error) {
+      isInitialized = false;
+    }
+    assert.isFalse(isInitialized);
+
+    const configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.ok(configs.superAdmin.equals(payer.publicKey));
+  });
+
+  it("Is reject role changes by anyone but the super admin!", async () => {
+    try {
+      await grantRole({ operator: {} }, stranger.publicKey, stranger.publicKey)
+        .signers([stranger])
+        .rpc();
+      assert.fail("only the super admin can grant roles");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSuperAdmin");
+    }
+  });
+
+  it("Is reject registration without the operator role!", async () => {
+    try {
+      await program.methods
+        .registerLotteryGame({
+          country: "KE",
+          lotteryGameName: "Lotto Ke Rogue",
+          lotteryWinningPercentage: 10000, // 100 % (basis points)
+          reservePercentage: 0,
+          operatorFeePercentage: null, // platform default
+          // single jackpot
+          prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+          operatorRevenueAccount: stranger.publicKey,
+          reserveAccount: stranger.publicKey,
+          unitCostOfLotteryTicket: new anchor.BN(1_000_000),
+          paymentMode: { sol: {} }, // tickets are paid in lamports
+          valueDate: "28-09-2024",
+          drawMethod: { commitReveal: {} },
+          gameMode: { raffle: {} },
+          pickCount: 0,
+          numberRange: 0,
+          drawCommitment: Array.from(Buffer.alloc(32, 1)),
+          oracleAuthority: anchor.web3.PublicKey.default,
+          rolloverRule: { none: {} },
+          claimPeriod: new anchor.BN(0),
+          claimDeadline: new anchor.BN(0), // no deadline
+          unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+          salesOpenAt: new anchor.BN(0),
+          salesCloseAt: new anchor.BN(1),
+          drawAt: new anchor.BN(1),
+          minTickets: new anchor.BN(0), // no minimum
+          maxTicketsPerPurchase: new anchor.BN(0), // no limit
+          maxTicketsPerGame: new anchor.BN(0), // no limit
+        })
+        .accounts({
+          owner: stranger.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+          lotteryOperator: lotteryOperator,
+          lotteryGameCounter: lotteryGameCounter,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          mintToken: null,
+          vaultTokens: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger, depositAccount])
+        .rpc();
+      assert.fail("only operator authorities can register lottery games");
+    } catch (error) {
+      assert.include(error.toString(), "MissingOperatorRole");
+    }
+  });
+
+  it("Is grant and revoke roles!", async () => {
+    const granted = await grantRole({ operator: {} }, stranger.publicKey).rpc();
+    await grantRole({ draw: {} }, stranger.publicKey).rpc();
+
+    const [grantedEvent] = await eventsOf(granted);
+    assert.equal(grantedEvent.name, "roleGranted");
+    assert.deepEqual(grantedEvent.data.role, { operator: {} });
+    assert.ok(grantedEvent.data.authority.equals(stranger.publicKey));
+    assert.ok(grantedEvent.data.superAdmin.equals(payer.publicKey));
+
+    let configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.ok(holdsRole(configs.operatorAuthorities));
+    assert.ok(holdsRole(configs.drawAuthorities));
+
+    try {
+      await grantRole({ operator: {} }, stranger.publicKey).rpc();
+      assert.fail("a role is only granted once");
+    } catch (error) {
+      assert.include(error.toString(), "RoleAlreadyGranted");
+    }
+
+    await revokeRole({ operator: {} }, stranger.publicKey).rpc();
+    const revoked = await revokeRole({ draw: {} }, stranger.publicKey).rpc();
+
+    const [revokedEvent] = await eventsOf(revoked);
+    assert.equal(revokedEvent.name, "roleRevoked");
+    assert.deepEqual(revokedEvent.data.role, { draw: {} });
+    assert.ok(revokedEvent.data.authority.equals(stranger.publicKey));
+    assert.ok(revokedEvent.data.superAdmin.equals(payer.publicKey));
+
+    configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.notOk(holdsRole(configs.operatorAuthorities));
+    assert.notOk(holdsRole(configs.drawAuthorities));
+
+    try {
+      await revokeRole({ operator: {} }, stranger.publicKey).rpc();
+      assert.fail("a role that isn't granted can't be revoked");
+    } catch (error) {
+      assert.include(error.toString(), "RoleNotGranted");
+    }
+  });
+
+  it("Is transfer the super admin in two steps!", async () => {
+    await program.methods
+      .proposeSuperAdmin({ superAdmin: newSuperAdmin.publicKey })
+      .accounts({
+        owner: payer.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .rpc();
+
+    // the super admin only changes once the proposed one accepts
+    let configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.ok(configs.superAdmin.equals(payer.publicKey));
+    assert.ok(configs.pendingSuperAdmin.equals(newSuperAdmin.publicKey));
+
+    try {
+      await program.methods
+        .acceptSuperAdmin()
+        .accounts({
+          owner: stranger.publicKey,
+          lotteryGameConfigs: lotteryGameConfigs,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("only the proposed super admin can accept");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidPendingSuperAdmin");
+    }
+
+    await program.methods
+      .acceptSuperAdmin()
+      .accounts({
+        owner: newSuperAdmin.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+      })
+      .signers([newSuperAdmin])
+      .rpc();
+
+    configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.ok(configs.superAdmin.equals(newSuperAdmin.publicKey));
+    assert.ok(
+      configs.pendingSuperAdmin.equals(anchor.web3.PublicKey.default)
+    );
+
+    // the previous super admin lost its rights
+    try {
+      await grantRole({ operator: {} }, stranger.publicKey).rpc();
+      assert.fail("only the super admin can grant roles");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSuperAdmin");
+    }
+
+    // hand the super admin back to the provider wallet for the other suites
+    await transferSuperAdmin(newSuperAdmin, payer);
+
+    configs = await program.account.lotteryGameConfigs.fetch(
+      lotteryGameConfigs
+    );
+    assert.ok(configs.superAdmin.equals(payer.publicKey));
+  });
+});
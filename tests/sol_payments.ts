@@ -0,0 +1,249 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitForDraw,
+  eventsOf,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("sol_payments", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+  const ticket = ticketAddress(lotteryGame, 0);
+
+  let rentExemptMinimum: number;
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    rentExemptMinimum =
+      await provider.connection.getMinimumBalanceForRentExemption(0);
+  });
+
+  it("Is register sol lottery game!", async () => {
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Sol",
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), // 1 sol in lamports
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount])
+      .rpc();
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.deepEqual(game.paymentMode, { sol: {} });
+    assert.ok(game.treasuryVault.equals(treasuryVault));
+    assert.equal(game.decimals, 9);
+
+    // the treasury vault is funded up to the rent-exempt minimum
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(vaultBalance, rentExemptMinimum);
+  });
+
+  it("Is buy lottery ticket with sol!", async () => {
+    const signature = await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    const [registered] = await eventsOf(signature);
+    assert.equal(registered.name, "participantRegistered");
+    assert.ok(registered.data.participant.equals(participant));
+    assert.ok(registered.data.owner.equals(participantOwner.publicKey));
+    assert.equal(registered.data.country, "KE");
+
+    await buyTicket(
+      participantOwner,
+      buyAccounts(participantOwner.publicKey, lotteryGame, lotteryRound, 0, {
+        treasuryVault,
+      }),
+      anchor.web3.LAMPORTS_PER_SOL
+    ).rpc();
+
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(
+      vaultBalance,
+      rentExemptMinimum + anchor.web3.LAMPORTS_PER_SOL
+    );
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(
+      game.totalAmountsRaised.toNumber(),
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+  });
+
+  it("Is withdraw lottery game winnings in sol!", async () => {
+    await waitForDraw(lotteryRound);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Sol",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const balanceBefore = await provider.connection.getBalance(
+      participantOwner.publicKey
+    );
+
+    // the only ticket sold wins the jackpot
+    const signature = await program.methods
+      .withdrawLotteryGameWinnings()
+      .accounts({
+        owner: participantOwner.publicKey,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        gameEntry: gameEntryAddress(lotteryGame, participant),
+        ticket: ticket,
+        senderTokens: null,
+        recipientTokens: null,
+        mintToken: null,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        tokenProgram: null,
+        associateTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      participantOwner.publicKey
+    );
+    // the prize less the transaction fee
+    assert.isAbove(
+      balanceAfter - balanceBefore,
+      anchor.web3.LAMPORTS_PER_SOL - 100_000
+    );
+
+    // the prize is recorded on the participant's entry in this game
+    const gameEntry = await program.account.gameEntry.fetch(
+      gameEntryAddress(lotteryGame, participant)
+    );
+    assert.equal(gameEntry.winnings.toNumber(), anchor.web3.LAMPORTS_PER_SOL);
+    assert.equal(gameEntry.prizesClaimed.toNumber(), 1);
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "winningsWithdrawn");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.ticketNumber.toNumber(), 0);
+    assert.ok(event.data.owner.equals(participantOwner.publicKey));
+    assert.equal(event.data.amount.toNumber(), anchor.web3.LAMPORTS_PER_SOL);
+
+    // payouts never take the treasury vault below the rent-exempt minimum
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(vaultBalance, rentExemptMinimum);
+  });
+});
@@ -0,0 +1,217 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  initConfigs,
+  grantRoles,
+  registerLotteryOperator,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  airdrop,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("ticket_accounting", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const participantOwner = anchor.web3.Keypair.generate();
+
+  const participant = participantAddress(participantOwner.publicKey);
+
+  // a sol lottery game with its own admin, so every game has game id 0
+  const newGame = (name: string, ticketPrice: number) => {
+    const owner = anchor.web3.Keypair.generate();
+    const depositAccount = anchor.web3.Keypair.generate();
+    const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+    const treasuryVault = treasuryVaultAddress(pdaAuth);
+    const lotteryOperator = lotteryOperatorAddress(owner.publicKey);
+    const lotteryGameCounter = lotteryGameCounterAddress(owner.publicKey);
+    const lotteryGame = lotteryGameAddress(owner.publicKey, 0);
+    const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+
+    return {
+      name,
+      ticketPrice,
+      owner,
+      depositAccount,
+      pdaAuth,
+      treasuryVault,
+      lotteryOperator,
+      lotteryGameCounter,
+      lotteryGame,
+      lotteryRound,
+    };
+  };
+
+  // priced at 5 lamports, a single ticket used to be recorded as 25 units
+  const oddPriceGame = newGame("Lotto Ke Odd", 5);
+  const largePriceGame = newGame("Lotto Ke Large", 333_333_333);
+
+  const registerGame = async (game: ReturnType<typeof newGame>) => {
+    await airdrop([game.owner], anchor.web3.LAMPORTS_PER_SOL);
+
+    await grantRoles(game.owner.publicKey);
+
+    await registerLotteryOperator(game.owner.publicKey);
+
+    await program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: game.name,
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(game.ticketPrice),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(
+          createHash("sha256").update(randomBytes(32)).digest()
+        ),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(600),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: game.owner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: game.lotteryOperator,
+        lotteryGameCounter: game.lotteryGameCounter,
+        lotteryGame: game.lotteryGame,
+        lotteryRound: game.lotteryRound,
+        depositAccount: game.depositAccount.publicKey,
+        pdaAuth: game.pdaAuth,
+        treasuryVault: game.treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([game.owner, game.depositAccount])
+      .rpc();
+  };
+
+  const buyTickets = (
+    game: ReturnType<typeof newGame>,
+    ticketNumber: number,
+    quantity: number
+  ) =>
+    buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        game.lotteryGame,
+        game.lotteryRound,
+        ticketNumber,
+        { treasuryVault: game.treasuryVault }
+      ),
+      quantity * game.ticketPrice,
+      quantity
+    ).rpc();
+
+  before(async () => {
+    await airdrop([participantOwner]);
+
+    await initConfigs();
+
+    await registerGame(oddPriceGame);
+    await registerGame(largePriceGame);
+
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+  });
+
+  it("Is count a ticket with an odd price as one unit!", async () => {
+    await buyTickets(oddPriceGame, 0, 1);
+
+    const account = await program.account.participant.fetch(participant);
+    assert.equal(account.totalUnitsLotteryTicket.toNumber(), 1);
+    assert.equal(account.totalAmountSpent.toNumber(), 5);
+
+    const ticket = await program.account.ticket.fetch(
+      ticketAddress(oddPriceGame.lotteryGame, 0)
+    );
+    assert.equal(ticket.quantity.toNumber(), 1);
+    assert.equal(ticket.amountPaid.toNumber(), 5);
+  });
+
+  it("Is count tickets and spend across multiple purchases!", async () => {
+    await buyTickets(largePriceGame, 0, 3);
+    await buyTickets(oddPriceGame, 1, 2);
+
+    // the participant's counters add up over every purchase in every game
+    const account = await program.account.participant.fetch(participant);
+    assert.equal(account.totalUnitsLotteryTicket.toNumber(), 6);
+    assert.equal(
+      account.totalAmountSpent.toNumber(),
+      3 * 5 + 3 * 333_333_333
+    );
+
+    // each game only counts its own tickets and proceeds
+    const oddGame = await program.account.lotteryGame.fetch(
+      oddPriceGame.lotteryGame
+    );
+    assert.equal(oddGame.ticketsSold.toNumber(), 3);
+    assert.equal(oddGame.totalAmountsRaised.toNumber(), 3 * 5);
+
+    const largeGame = await program.account.lotteryGame.fetch(
+      largePriceGame.lotteryGame
+    );
+    assert.equal(largeGame.ticketsSold.toNumber(), 3);
+    assert.equal(largeGame.totalAmountsRaised.toNumber(), 3 * 333_333_333);
+
+    const oddRound = await program.account.lotteryRound.fetch(
+      oddPriceGame.lotteryRound
+    );
+    assert.equal(oddRound.ticketsSold.toNumber(), 3);
+    assert.equal(oddRound.amountsRaised.toNumber(), 3 * 5);
+
+    // the participant's entry in each game only counts that game's purchases
+    const oddEntry = await program.account.gameEntry.fetch(
+      gameEntryAddress(oddPriceGame.lotteryGame, participant)
+    );
+    assert.equal(oddEntry.ticketsBought.toNumber(), 3);
+    assert.equal(oddEntry.amountSpent.toNumber(), 3 * 5);
+
+    const largeEntry = await program.account.gameEntry.fetch(
+      gameEntryAddress(largePriceGame.lotteryGame, participant)
+    );
+    assert.equal(largeEntry.ticketsBought.toNumber(), 3);
+    assert.equal(largeEntry.amountSpent.toNumber(), 3 * 333_333_333);
+  });
+});
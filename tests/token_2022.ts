@@ -0,0 +1,448 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import {
+  AccountState,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeDefaultAccountStateInstruction,
+  createInitializeMintInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferHookInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getAccount,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  setupLotteryGameOwner,
+  airdrop,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  treasuryVaultAddress,
+  vaultTokensAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("token_2022", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+  const mintKeypair = anchor.web3.Keypair.generate();
+  const mint = mintKeypair.publicKey;
+
+  // 1 % transfer fee, capped well above the ticket price
+  const transferFeeBasisPoints = 100;
+  const maximumFee = BigInt(1_000_000_000_000);
+  const decimals = 6;
+  const ticketPrice = 10_000_000; // 10 tokens with 6 decimals
+
+  const drawCommitment = createHash("sha256")
+    .update(randomBytes(32))
+    .digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+  const vaultTokens = vaultTokensAddress(lotteryGame);
+
+  let participantATA: anchor.web3.PublicKey;
+
+  // pays from the participant's token account unless told otherwise
+  const buy = (
+    ticketNumber: number,
+    payment: {
+      senderTokens?: anchor.web3.PublicKey;
+      mintToken?: anchor.web3.PublicKey;
+    } = {}
+  ) =>
+    buyTicket(
+      participantOwner,
+      buyAccounts(
+        participantOwner.publicKey,
+        lotteryGame,
+        lotteryRound,
+        ticketNumber,
+        {
+          senderTokens: participantATA,
+          mintToken: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...payment,
+        }
+      ),
+      ticketPrice
+    ).rpc();
+
+  // Token-2022 mint initialized with the given extensions
+  const createMint2022 = async (
+    keypair: anchor.web3.Keypair,
+    extensions: ExtensionType[],
+    initializeExtensions: anchor.web3.TransactionInstruction[]
+  ) => {
+    const mintLen = getMintLen(extensions);
+    const lamports =
+      await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    const transaction = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: keypair.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      ...initializeExtensions,
+      createInitializeMintInstruction(
+        keypair.publicKey,
+        decimals,
+        payer.publicKey,
+        payer.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await anchor.web3.sendAndConfirmTransaction(
+      provider.connection,
+      transaction,
+      [payer, keypair]
+    );
+  };
+
+  // registers the lottery owner's next lottery game, paid in the given mint
+  const registerLotteryGame = (
+    gameId: number,
+    mintToken: anchor.web3.PublicKey,
+    deposit: anchor.web3.Keypair
+  ) => {
+    const game = lotteryGameAddress(lotteryGameOwner.publicKey, gameId);
+    const depositPdaAuth = pdaAuthAddress(deposit.publicKey);
+    return program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke 2022",
+        lotteryWinningPercentage: 9000, // 90 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(ticketPrice), // 10 tokens in base units (6 decimals)
+        paymentMode: { token: {} }, // tickets are paid in the token mint
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: { none: {} },
+        claimPeriod: new anchor.BN(0),
+        claimDeadline: new anchor.BN(0), // no deadline
+        unclaimedPrizesBeneficiary: anchor.web3.PublicKey.default,
+        ...schedule(600),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: game,
+        lotteryRound: lotteryRoundAddress(game, 0),
+        depositAccount: deposit.publicKey,
+        pdaAuth: depositPdaAuth,
+        treasuryVault: treasuryVaultAddress(depositPdaAuth),
+        mintToken: mintToken,
+        vaultTokens: vaultTokensAddress(game),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, deposit]);
+  };
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    // Token-2022 mint with the transfer fee extension
+    await createMint2022(
+      mintKeypair,
+      [ExtensionType.TransferFeeConfig],
+      [
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          payer.publicKey,
+          payer.publicKey,
+          transferFeeBasisPoints,
+          maximumFee,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ]
+    );
+
+    participantATA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        participantOwner.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      participantATA,
+      payer,
+      100_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  it("Is register lottery game with a Token-2022 mint!", async () => {
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+
+    await registerLotteryGame(0, mint, depositAccount).rpc();
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.ok(game.mint.equals(mint));
+    assert.equal(game.decimals, decimals);
+
+    const vault = await getAccount(
+      provider.connection,
+      vaultTokens,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.ok(vault.owner.equals(treasuryVault));
+  });
+
+  it("Is buy lottery ticket with a transfer fee mint!", async () => {
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    const balanceBefore = (
+      await getAccount(
+        provider.connection,
+        participantATA,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).amount;
+
+    await buy(0);
+
+    // the vault receives the full ticket price, the buyer pays the fee on top
+    const vault = await getAccount(
+      provider.connection,
+      vaultTokens,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(Number(vault.amount), ticketPrice);
+
+    const balanceAfter = (
+      await getAccount(
+        provider.connection,
+        participantATA,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).amount;
+    assert.isAbove(Number(balanceBefore - balanceAfter), ticketPrice);
+
+    // pool accounting matches what the vault received
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.totalAmountsRaised.toNumber(), ticketPrice);
+    assert.equal(game.totalAvailableFunds.toNumber(), ticketPrice);
+  });
+
+  it("Is reject a look-alike mint!", async () => {
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.ok(game.mint.equals(mint));
+    assert.equal(game.decimals, decimals);
+
+    // same decimals and token program as the game's mint, but another mint
+    const lookAlikeMint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      decimals,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const lookAlikeATA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        lookAlikeMint,
+        participantOwner.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      lookAlikeMint,
+      lookAlikeATA,
+      payer,
+      100_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    try {
+      await buy(1, { senderTokens: lookAlikeATA, mintToken: lookAlikeMint });
+      assert.fail("buying with a look-alike mint should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMint");
+    }
+
+    try {
+      await buy(1, { senderTokens: lookAlikeATA });
+      assert.fail("paying from a look-alike token account should fail");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMint");
+    }
+
+    const updatedGame = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(updatedGame.ticketsSold.toNumber(), 1);
+  });
+
+  it("Is reject mints with unsupported extensions!", async () => {
+    const delegate = anchor.web3.Keypair.generate().publicKey;
+    const transferHookProgram = anchor.web3.Keypair.generate().publicKey;
+    const extensions: [
+      string,
+      ExtensionType,
+      (mintToken: anchor.web3.PublicKey) => anchor.web3.TransactionInstruction
+    ][] = [
+      [
+        "permanent delegate",
+        ExtensionType.PermanentDelegate,
+        (mintToken) =>
+          createInitializePermanentDelegateInstruction(
+            mintToken,
+            delegate,
+            TOKEN_2022_PROGRAM_ID
+          ),
+      ],
+      [
+        "transfer hook",
+        ExtensionType.TransferHook,
+        (mintToken) =>
+          createInitializeTransferHookInstruction(
+            mintToken,
+            payer.publicKey,
+            transferHookProgram,
+            TOKEN_2022_PROGRAM_ID
+          ),
+      ],
+      [
+        "non-transferable",
+        ExtensionType.NonTransferable,
+        (mintToken) =>
+          createInitializeNonTransferableMintInstruction(
+            mintToken,
+            TOKEN_2022_PROGRAM_ID
+          ),
+      ],
+      [
+        "frozen default account state",
+        ExtensionType.DefaultAccountState,
+        (mintToken) =>
+          createInitializeDefaultAccountStateInstruction(
+            mintToken,
+            AccountState.Frozen,
+            TOKEN_2022_PROGRAM_ID
+          ),
+      ],
+    ];
+
+    for (const [name, extension, initializeExtension] of extensions) {
+      const keypair = anchor.web3.Keypair.generate();
+      await createMint2022(
+        keypair,
+        [extension],
+        [initializeExtension(keypair.publicKey)]
+      );
+
+      try {
+        await registerLotteryGame(
+          1,
+          keypair.publicKey,
+          anchor.web3.Keypair.generate()
+        ).rpc();
+        assert.fail(`registering a ${name} mint should fail`);
+      } catch (error) {
+        assert.include(error.toString(), "UnsupportedMintExtension");
+      }
+    }
+
+    // an initialized default account state is fine
+    const keypair = anchor.web3.Keypair.generate();
+    await createMint2022(
+      keypair,
+      [ExtensionType.DefaultAccountState],
+      [
+        createInitializeDefaultAccountStateInstruction(
+          keypair.publicKey,
+          AccountState.Initialized,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ]
+    );
+    await registerLotteryGame(
+      1,
+      keypair.publicKey,
+      anchor.web3.Keypair.generate()
+    ).rpc();
+
+    const game = await program.account.lotteryGame.fetch(
+      lotteryGameAddress(lotteryGameOwner.publicKey, 1)
+    );
+    assert.ok(game.mint.equals(keypair.publicKey));
+  });
+});
@@ -0,0 +1,305 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LotteryPlatform } from "../target/types/lottery_platform";
+import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  schedule,
+  waitUntil,
+  eventsOf,
+  setupLotteryGameOwner,
+  airdrop,
+  associateTokenProgram,
+  gameEntryAddress,
+  lotteryGameAddress,
+  lotteryGameConfigs,
+  lotteryGameCounterAddress,
+  lotteryOperatorAddress,
+  lotteryRoundAddress,
+  participantAddress,
+  pdaAuthAddress,
+  ticketAddress,
+  treasuryVaultAddress,
+  buyAccounts,
+  buyTicket,
+} from "./helpers";
+
+describe("unclaimed_prizes", () => {
+  let provider = anchor.AnchorProvider.local("http://127.0.0.1:8899");
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const program = anchor.workspace.LotteryPlatform as Program<LotteryPlatform>;
+  const lotteryGameOwner = anchor.web3.Keypair.generate();
+  const depositAccount = anchor.web3.Keypair.generate();
+  const participantOwner = anchor.web3.Keypair.generate();
+  // good-causes fund receiving prizes unclaimed by the deadline
+  const beneficiary = anchor.web3.Keypair.generate();
+
+  const ticketPrice = anchor.web3.LAMPORTS_PER_SOL; // 1 sol in lamports
+  const claimDeadline = 2; // seconds after the draw
+
+  const drawSeed = randomBytes(32);
+  const drawCommitment = createHash("sha256").update(drawSeed).digest();
+
+  const pdaAuth = pdaAuthAddress(depositAccount.publicKey);
+  const treasuryVault = treasuryVaultAddress(pdaAuth);
+  const lotteryOperator = lotteryOperatorAddress(lotteryGameOwner.publicKey);
+  const lotteryGameCounter = lotteryGameCounterAddress(
+    lotteryGameOwner.publicKey
+  );
+  const lotteryGame = lotteryGameAddress(lotteryGameOwner.publicKey, 0);
+  const lotteryRound = lotteryRoundAddress(lotteryGame, 0);
+  const participant = participantAddress(participantOwner.publicKey);
+  const gameEntry = gameEntryAddress(lotteryGame, participant);
+  const ticket = ticketAddress(lotteryGame, 0);
+
+  const sweepAccounts = (owner: anchor.web3.PublicKey) => ({
+    owner: owner,
+    lotteryGameConfigs: lotteryGameConfigs,
+    lotteryGame: lotteryGame,
+    lotteryRound: lotteryRound,
+    senderTokens: null,
+    beneficiaryTokens: null,
+    mintToken: null,
+    beneficiaryWallet: beneficiary.publicKey,
+    depositAccount: depositAccount.publicKey,
+    pdaAuth: pdaAuth,
+    treasuryVault: treasuryVault,
+    tokenProgram: null,
+    systemProgram: anchor.web3.SystemProgram.programId,
+  });
+
+  const registerGame = (rolloverRule: object, claimPeriod: number) =>
+    program.methods
+      .registerLotteryGame({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Deadline",
+        lotteryWinningPercentage: 10000, // 100 % (basis points)
+        reservePercentage: 0,
+        operatorFeePercentage: null, // platform default
+        // single jackpot
+        prizeTiers: [{ winners: 1, matches: 0, prizePercentage: 10000 }],
+        operatorRevenueAccount: anchor.web3.Keypair.generate().publicKey,
+        reserveAccount: anchor.web3.Keypair.generate().publicKey,
+        unitCostOfLotteryTicket: new anchor.BN(ticketPrice),
+        paymentMode: { sol: {} }, // tickets are paid in lamports
+        valueDate: "28-09-2024",
+        drawMethod: { commitReveal: {} },
+        gameMode: { raffle: {} },
+        pickCount: 0,
+        numberRange: 0,
+        drawCommitment: Array.from(drawCommitment),
+        oracleAuthority: anchor.web3.PublicKey.default,
+        rolloverRule: rolloverRule,
+        claimPeriod: new anchor.BN(claimPeriod),
+        claimDeadline: new anchor.BN(claimDeadline),
+        unclaimedPrizesBeneficiary: beneficiary.publicKey,
+        ...schedule(8),
+        minTickets: new anchor.BN(0), // no minimum
+        maxTicketsPerPurchase: new anchor.BN(0), // no limit
+        maxTicketsPerGame: new anchor.BN(0), // no limit
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGameCounter: lotteryGameCounter,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        mintToken: null,
+        vaultTokens: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner, depositAccount]);
+
+  let rentExemptMinimum: number;
+
+  before(async () => {
+    await airdrop([lotteryGameOwner, participantOwner]);
+
+    rentExemptMinimum =
+      await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    await setupLotteryGameOwner(lotteryGameOwner.publicKey);
+  });
+
+  it("Is register a lottery game with a claim deadline!", async () => {
+    // unclaimed prizes can't roll over before their winners had time to claim
+    try {
+      await registerGame({ unwonAndUnclaimed: {} }, 0).rpc();
+      assert.fail("a rollover of unclaimed prizes needs a claim period");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidClaimPeriod");
+    }
+
+    // nor while they can still be claimed before the deadline
+    try {
+      await registerGame({ unwonAndUnclaimed: {} }, claimDeadline - 1).rpc();
+      assert.fail("the claim period can't end before the claim deadline");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidClaimDeadline");
+    }
+
+    await registerGame({ none: {} }, 0).rpc();
+
+    await program.methods
+      .registerParticipant({ fullNames: "john doe", country: "KE" })
+      .accounts({
+        owner: participantOwner.publicKey,
+        participant: participant,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([participantOwner])
+      .rpc();
+
+    await buyTicket(
+      participantOwner,
+      buyAccounts(participantOwner.publicKey, lotteryGame, lotteryRound, 0, {
+        treasuryVault,
+      }),
+      ticketPrice
+    ).rpc();
+  });
+
+  it("Is reject sweeping prizes before the claim deadline!", async () => {
+    const { drawAt } = await program.account.lotteryRound.fetch(lotteryRound);
+    await waitUntil(drawAt.toNumber() - 1);
+
+    await program.methods
+      .getLotteryGameWinner({
+        country: "KE",
+        lotteryGameName: "Lotto Ke Deadline",
+      })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    // the entropy slot must be produced after sales were closed
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    await program.methods
+      .revealLotteryGameWinner({ seed: Array.from(drawSeed) })
+      .accounts({
+        owner: lotteryGameOwner.publicKey,
+        lotteryGameConfigs: lotteryGameConfigs,
+        lotteryOperator: lotteryOperator,
+        lotteryGame: lotteryGame,
+        lotteryRound: lotteryRound,
+        recentSlothashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    try {
+      await program.methods
+        .sweepUnclaimedPrizes()
+        .accounts(sweepAccounts(lotteryGameOwner.publicKey))
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("prizes can still be claimed before the deadline");
+    } catch (error) {
+      assert.include(error.toString(), "ClaimDeadlineNotPassed");
+    }
+  });
+
+  it("Is reject claims after the claim deadline!", async () => {
+    const { drawnAt } = await program.account.lotteryRound.fetch(lotteryRound);
+    await waitUntil(drawnAt.toNumber() + claimDeadline);
+
+    // the only ticket sold won the jackpot but claims too late
+    try {
+      await program.methods
+        .withdrawLotteryGameWinnings()
+        .accounts({
+          owner: participantOwner.publicKey,
+          lotteryGame: lotteryGame,
+          lotteryRound: lotteryRound,
+          gameEntry: gameEntry,
+          ticket: ticket,
+          senderTokens: null,
+          recipientTokens: null,
+          mintToken: null,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          tokenProgram: null,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([participantOwner])
+        .rpc();
+      assert.fail("claims after the deadline should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "ClaimDeadlinePassed");
+    }
+  });
+
+  it("Is sweep unclaimed prizes to the beneficiary!", async () => {
+    // only the lottery game admin can sweep unclaimed prizes
+    try {
+      await program.methods
+        .sweepUnclaimedPrizes()
+        .accounts(sweepAccounts(participantOwner.publicKey))
+        .signers([participantOwner])
+        .rpc();
+      assert.fail("only the admin can sweep unclaimed prizes");
+    } catch (error) {
+      assert.include(error.toString(), "MissingOperatorRole");
+    }
+
+    const signature = await program.methods
+      .sweepUnclaimedPrizes()
+      .accounts(sweepAccounts(lotteryGameOwner.publicKey))
+      .signers([lotteryGameOwner])
+      .rpc();
+
+    const [event] = await eventsOf(signature);
+    assert.equal(event.name, "unclaimedPrizesSwept");
+    assert.ok(event.data.lotteryGame.equals(lotteryGame));
+    assert.equal(event.data.roundNumber.toNumber(), 0);
+    assert.ok(event.data.beneficiary.equals(beneficiary.publicKey));
+    assert.equal(event.data.amount.toNumber(), ticketPrice);
+
+    const beneficiaryBalance = await provider.connection.getBalance(
+      beneficiary.publicKey
+    );
+    assert.equal(beneficiaryBalance, ticketPrice);
+
+    const vaultBalance = await provider.connection.getBalance(treasuryVault);
+    assert.equal(vaultBalance, rentExemptMinimum);
+
+    const round = await program.account.lotteryRound.fetch(lotteryRound);
+    assert.equal(round.sweptAmount.toNumber(), ticketPrice);
+    assert.equal(round.availableFunds.toNumber(), 0);
+
+    const game = await program.account.lotteryGame.fetch(lotteryGame);
+    assert.equal(game.totalAmountsSwept.toNumber(), ticketPrice);
+
+    // a round's unclaimed prizes are only swept once
+    try {
+      await program.methods
+        .sweepUnclaimedPrizes()
+        .accounts(sweepAccounts(lotteryGameOwner.publicKey))
+        .signers([lotteryGameOwner])
+        .rpc();
+      assert.fail("a second sweep should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "NothingToSweep");
+    }
+  });
+});